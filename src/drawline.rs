@@ -1,51 +1,65 @@
-use std::f32;
+use std::f32::consts::FRAC_PI_4;
 use std::rc::Rc;
 
 use nannou::geom::Point2;
 
 use crate::{
-    matrix::Matrix,
-    solid::{Line, Point, Scene, ScreenParameter},
+    matrix::fixed::Matrix4,
+    solid::{
+        Line, Orthographic, Perspective, Point, Projection, Scene, ScreenParameter, ScreenVertex,
+    },
     transformations::{basic_scale, translate},
+    vector::{Vec3, Vec4},
 };
 
+/// Smallest w a clipped endpoint may keep. The legacy projection has no near
+/// plane, so without this a line through the eye would be trimmed to w = 0.
+const MIN_CLIP_W: f32 = 1e-5;
+
+/// Elevation of the isometric view direction, `asin(1 / sqrt(3))`.
+const ISOMETRIC_ELEVATION: f32 = 0.615_479_7;
+
+/// Which side of the front view the other views are drawn on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum ViewArrangement {
+    /// Top view below the front view, left-side view to its right.
+    FirstAngle,
+    /// Top view above the front view, right-side view to its right.
+    ThirdAngle,
+}
+
+/// Front, top and side views of one scene, each already placed on the sheet.
+#[allow(dead_code)]
+pub struct MultiView {
+    pub front: Scene<Point2>,
+    pub top: Scene<Point2>,
+    pub side: Scene<Point2>,
+}
+
 pub fn get_sin_x(x: f32, y: f32, z: f32) -> Option<f32> {
-    if x + y + z == 0.0 {
-        return None;
-    } else {
-        let result = z / f32::sqrt(f32::powi(x, 2) + f32::powi(y, 2) + f32::powi(z, 2));
-        return Some(result);
-    }
+    let eye = Vec3::new(x, y, z);
+    return eye.normalize().map(|v| v.z);
 }
 
 pub fn get_cos_x(x: f32, y: f32, z: f32) -> Option<f32> {
-    if x + y + z == 0.0 {
+    let eye = Vec3::new(x, y, z);
+    let length = eye.length();
+    if length == 0.0 {
         return None;
-    } else {
-        let result = f32::sqrt(f32::powi(x, 2) + f32::powi(y, 2))
-            / f32::sqrt(f32::powi(x, 2) + f32::powi(y, 2) + f32::powi(z, 2));
-        return Some(result);
     }
+    return Some(Vec3::new(x, y, 0.0).length() / length);
 }
 
 pub fn get_sin_y(x: f32, y: f32) -> Option<f32> {
-    if x + y == 0.0 {
-        return None;
-    }
-    let result = x / f32::sqrt(f32::powi(x, 2) + f32::powi(y, 2));
-    return Some(result);
+    return Vec3::new(x, y, 0.0).normalize().map(|v| v.x);
 }
 
 pub fn get_cos_y(x: f32, y: f32) -> Option<f32> {
-    if x + y == 0.0 {
-        return None;
-    }
-    let result = y / f32::sqrt(f32::powi(x, 2) + f32::powi(y, 2));
-
-    return Some(result);
+    return Vec3::new(x, y, 0.0).normalize().map(|v| v.y);
 }
 
-pub fn get_view_matrix(x: f32, y: f32, z: f32) -> Matrix {
+pub fn get_view_matrix(x: f32, y: f32, z: f32) -> Matrix4 {
     let sin_x = get_sin_x(x, y, z).unwrap_or(0.0);
     let cos_x = get_cos_x(x, y, z).unwrap_or(0.0);
     let sin_y = get_sin_y(x, y).unwrap_or(0.0);
@@ -53,72 +67,247 @@ pub fn get_view_matrix(x: f32, y: f32, z: f32) -> Matrix {
 
     let t1 = translate(-x, -y, -z);
 
-    let mut t2 = Matrix::new(4, 4);
-    t2.set(0, 0, 1.0);
-    t2.set(1, 2, -1.0);
-    t2.set(2, 1, 1.0);
-    t2.set(3, 3, 1.0);
-
-    let mut t3 = Matrix::new(4, 4);
-    t3.set(0, 0, -cos_y);
-    t3.set(0, 2, sin_y);
-    t3.set(1, 1, 1.0);
-    t3.set(2, 0, -sin_y);
-    t3.set(2, 2, -cos_y);
-    t3.set(3, 3, 1.0);
-
-    let mut t4 = Matrix::new(4, 4);
-    t4.set(0, 0, 1.0);
-    t4.set(1, 1, cos_x);
-    t4.set(1, 2, sin_x);
-    t4.set(2, 1, -sin_x);
-    t4.set(2, 2, cos_x);
-    t4.set(3, 3, 1.0);
-
-    let mut t5 = Matrix::new(4, 4);
-    t5.set(0, 0, 1.0);
-    t5.set(1, 1, 1.0);
-    t5.set(2, 2, -1.0);
-    t5.set(3, 3, 1.0);
-
-    let r = t1
-        .matrix_multiply(&t2)
-        .unwrap()
-        .matrix_multiply(&t3)
-        .unwrap()
-        .matrix_multiply(&t4)
-        .unwrap()
-        .matrix_multiply(&t5)
-        .unwrap();
+    let mut t2 = Matrix4::new();
+    t2[(0, 0)] = 1.0;
+    t2[(1, 2)] = -1.0;
+    t2[(2, 1)] = 1.0;
+    t2[(3, 3)] = 1.0;
+
+    let mut t3 = Matrix4::new();
+    t3[(0, 0)] = -cos_y;
+    t3[(0, 2)] = sin_y;
+    t3[(1, 1)] = 1.0;
+    t3[(2, 0)] = -sin_y;
+    t3[(2, 2)] = -cos_y;
+    t3[(3, 3)] = 1.0;
+
+    let mut t4 = Matrix4::new();
+    t4[(0, 0)] = 1.0;
+    t4[(1, 1)] = cos_x;
+    t4[(1, 2)] = sin_x;
+    t4[(2, 1)] = -sin_x;
+    t4[(2, 2)] = cos_x;
+    t4[(3, 3)] = 1.0;
+
+    let mut t5 = Matrix4::new();
+    t5[(0, 0)] = 1.0;
+    t5[(1, 1)] = 1.0;
+    t5[(2, 2)] = -1.0;
+    t5[(3, 3)] = 1.0;
+
+    let r = t1 * t2 * t3 * t4 * t5;
 
     return r;
 }
 
-pub fn get_clip_matrix(view_angle: f32) -> Matrix {
+/// Scales x and y by the view angle and copies the view-space depth into w,
+/// so that the perspective divide yields `x * ds / z` and `y * ds / z`.
+pub fn get_clip_matrix(view_angle: f32) -> Matrix4 {
     let ds = view_angle;
-    let r = basic_scale(ds, ds, 1.0);
+    let mut r = basic_scale(ds, ds, 1.0);
+    r[(2, 3)] = 1.0;
+    r[(3, 3)] = 0.0;
 
     return r;
 }
 
-pub fn get_clipping_coordinates(
-    eye_coordinates: [f32; 3],
-    view_angle: f32,
-    scene: &Scene<&Point>,
-) -> Scene<Point> {
-    let view_matrix = get_view_matrix(eye_coordinates[0], eye_coordinates[1], eye_coordinates[2]);
-    let clip_matrix = get_clip_matrix(view_angle);
+/// Perspective projection onto the canonical clip volume. View space looks
+/// along +z, so w is the view depth; after the divide, depth runs from -1 at
+/// `near` to 1 at `far`.
+pub fn get_perspective_matrix(perspective: &Perspective) -> Matrix4 {
+    let Perspective {
+        fov_y,
+        aspect,
+        near,
+        far,
+    } = *perspective;
+    let f = 1.0 / (fov_y / 2.0).tan();
+
+    let mut r = Matrix4::new();
+    r[(0, 0)] = f / aspect;
+    r[(1, 1)] = f;
+    r[(2, 2)] = (far + near) / (far - near);
+    r[(2, 3)] = 1.0;
+    r[(3, 2)] = -2.0 * far * near / (far - near);
+
+    return r;
+}
+
+/// Parallel projection of `volume` onto the canonical clip volume, with
+/// depth -1 at `near` and 1 at `far`.
+pub fn get_orthographic_matrix(volume: &Orthographic) -> Matrix4 {
+    let Orthographic {
+        width,
+        height,
+        near,
+        far,
+    } = *volume;
+
+    let mut r = basic_scale(2.0 / width, 2.0 / height, 2.0 / (far - near));
+    r[(3, 2)] = -(far + near) / (far - near);
+
+    return r;
+}
+
+/// View-space shear for oblique projections. A point `t` behind the plane
+/// at view depth `depth` moves `depth_scale * t` along the direction `angle`
+/// radians above the x axis.
+pub fn get_oblique_matrix(depth_scale: f32, angle: f32, depth: f32) -> Matrix4 {
+    let (sin, cos) = angle.sin_cos();
+
+    let mut r = Matrix4::identity();
+    r[(2, 0)] = depth_scale * cos;
+    r[(2, 1)] = depth_scale * sin;
+    r[(3, 0)] = -depth_scale * cos * depth;
+    r[(3, 1)] = -depth_scale * sin * depth;
+
+    return r;
+}
+
+/// World-to-view matrix for a camera at `eye` looking at `target`. View space
+/// matches `get_view_matrix`: x right, y towards `up`, z into the screen.
+pub fn get_look_at_matrix(eye: Vec3, target: Vec3, up: Vec3) -> Matrix4 {
+    let forward = (target - eye)
+        .normalize()
+        .unwrap_or(Vec3::new(0.0, 0.0, 1.0));
+    let right = forward
+        .cross(up)
+        .normalize()
+        .or_else(|| forward.cross(Vec3::new(0.0, 1.0, 0.0)).normalize())
+        .unwrap_or(Vec3::new(1.0, 0.0, 0.0));
+    let up = right.cross(forward);
+
+    let r = Matrix4::from_array([
+        [right.x, up.x, forward.x, 0.0],
+        [right.y, up.y, forward.y, 0.0],
+        [right.z, up.z, forward.z, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+
+    return translate(-eye.x, -eye.y, -eye.z) * r;
+}
+
+/// World-to-view matrix selected by the screen parameters.
+pub fn get_camera_matrix(screen_parameter: &ScreenParameter) -> Matrix4 {
+    let eye = Vec3::from(screen_parameter.get_eye_coordinates());
+    let distance = eye.length();
+    let z_up = Vec3::new(0.0, 0.0, 1.0);
+
+    let from_direction = |azimuth: f32, elevation: f32| {
+        let (sin_a, cos_a) = azimuth.sin_cos();
+        let (sin_e, cos_e) = elevation.sin_cos();
+        let eye = Vec3::new(cos_e * cos_a, cos_e * sin_a, sin_e) * distance;
+        return get_look_at_matrix(eye, Vec3::zero(), z_up);
+    };
+
+    return match screen_parameter.get_projection() {
+        Projection::Legacy | Projection::Perspective(_) | Projection::Orthographic(_) => {
+            get_view_matrix(eye.x, eye.y, eye.z)
+        }
+        Projection::Isometric(_) => from_direction(FRAC_PI_4, ISOMETRIC_ELEVATION),
+        Projection::Dimetric { elevation, .. } => from_direction(FRAC_PI_4, elevation),
+        Projection::Trimetric {
+            azimuth, elevation, ..
+        } => from_direction(azimuth, elevation),
+        Projection::Cavalier { .. } | Projection::Cabinet { .. } => {
+            get_look_at_matrix(Vec3::new(0.0, -distance, 0.0), Vec3::zero(), z_up)
+        }
+    };
+}
+
+/// The view-to-clip matrix selected by the screen parameters.
+pub fn get_projection_matrix(screen_parameter: &ScreenParameter) -> Matrix4 {
+    let distance = Vec3::from(screen_parameter.get_eye_coordinates()).length();
+
+    return match screen_parameter.get_projection() {
+        Projection::Legacy => get_clip_matrix(screen_parameter.get_view_angle()),
+        Projection::Perspective(perspective) => get_perspective_matrix(&perspective),
+        Projection::Orthographic(volume)
+        | Projection::Isometric(volume)
+        | Projection::Dimetric { volume, .. }
+        | Projection::Trimetric { volume, .. } => get_orthographic_matrix(&volume),
+        Projection::Cavalier { volume, angle } => {
+            get_oblique_matrix(1.0, angle, distance) * get_orthographic_matrix(&volume)
+        }
+        Projection::Cabinet { volume, angle } => {
+            get_oblique_matrix(0.5, angle, distance) * get_orthographic_matrix(&volume)
+        }
+    };
+}
 
-    let result_matrix = view_matrix.matrix_multiply(&clip_matrix).unwrap();
+/// Transforms the scene into clip space with a world-to-clip matrix, keeping
+/// the homogeneous w of every endpoint for `clip_scene` and the perspective
+/// divide.
+pub fn get_clipping_coordinates(clip_matrix: &Matrix4, scene: &Scene<&Point>) -> Scene<Vec4> {
+    let endpoints = scene
+        .lines
+        .iter()
+        .flat_map(|line| [*line.a, *line.b])
+        .collect::<Vec<&Point>>();
+    let mut transformed =
+        Point::apply_matrix_batch_homogeneous(&endpoints, clip_matrix).into_iter();
+
+    let mut clipped_lines = Vec::with_capacity(scene.lines.len());
+    while let (Some(a), Some(b)) = (transformed.next(), transformed.next()) {
+        clipped_lines.push(Line {
+            a: Rc::new(a),
+            b: Rc::new(b),
+        });
+    }
+
+    return Scene {
+        num_lines: clipped_lines.len(),
+        lines: clipped_lines,
+    };
+}
+
+/// Liang-Barsky clipping of a clip-space segment against the six planes of
+/// the canonical volume `-w <= x, y, z <= w`, plus `w >= MIN_CLIP_W`.
+/// Returns `None` when no part of the segment is visible. Endpoints inside
+/// the volume are returned unchanged, so lines behind the camera, which
+/// have negative w, are cut off or dropped before the divide.
+pub fn clip_line(a: Vec4, b: Vec4) -> Option<(Vec4, Vec4)> {
+    let boundaries = |v: Vec4| {
+        return [
+            v.w + v.x,
+            v.w - v.x,
+            v.w + v.y,
+            v.w - v.y,
+            v.w + v.z,
+            v.w - v.z,
+            v.w - MIN_CLIP_W,
+        ];
+    };
 
+    let (mut t0, mut t1) = (0.0f32, 1.0f32);
+    for (da, db) in boundaries(a).into_iter().zip(boundaries(b)) {
+        if da < 0.0 && db < 0.0 {
+            return None;
+        }
+        if da < 0.0 {
+            t0 = t0.max(da / (da - db));
+        } else if db < 0.0 {
+            t1 = t1.min(da / (da - db));
+        }
+        if t0 > t1 {
+            return None;
+        }
+    }
+
+    let clipped_a = if t0 > 0.0 { a.lerp(b, t0) } else { a };
+    let clipped_b = if t1 < 1.0 { a.lerp(b, t1) } else { b };
+
+    return Some((clipped_a, clipped_b));
+}
+
+/// Clips every line of a clip-space scene, dropping the invisible ones.
+pub fn clip_scene(scene: &Scene<Vec4>) -> Scene<Vec4> {
     let clipped_lines = scene
         .lines
         .iter()
-        .map(|line| Line {
-            a: Rc::new(line.a.apply_matrix(&result_matrix)),
-            b: Rc::new(line.b.apply_matrix(&result_matrix)),
-        })
-        .collect::<Vec<Line<Point>>>();
+        .filter_map(|line| clip_line(*line.a, *line.b))
+        .map(|(a, b)| Line::new(a, b))
+        .collect::<Vec<Line<Vec4>>>();
 
     return Scene {
         num_lines: clipped_lines.len(),
@@ -126,21 +315,22 @@ pub fn get_clipping_coordinates(
     };
 }
 
+/// Divides clipped lines by w and maps them onto the window.
 pub fn get_screen_coordinates(
-    scene: &Scene<Point>,
+    scene: &Scene<Vec4>,
     vsx: f32,
     vsy: f32,
     vcx: f32,
     vcy: f32,
-) -> Scene<Point2> {
+) -> Scene<ScreenVertex> {
     let screen_lines = scene
         .lines
         .iter()
         .map(|line| Line {
-            a: Rc::new(line.a.to_screen_point(vsx, vsy, vcx, vcy)),
-            b: Rc::new(line.b.to_screen_point(vsx, vsy, vcx, vcy)),
+            a: Rc::new(ScreenVertex::from_clip(*line.a, vsx, vsy, vcx, vcy)),
+            b: Rc::new(ScreenVertex::from_clip(*line.b, vsx, vsy, vcx, vcy)),
         })
-        .collect::<Vec<Line<Point2>>>();
+        .collect::<Vec<Line<ScreenVertex>>>();
 
     return Scene {
         num_lines: screen_lines.len(),
@@ -148,35 +338,119 @@ pub fn get_screen_coordinates(
     };
 }
 
-pub fn create_scene(scene: Scene<&Point>, screen_parameter: &ScreenParameter) -> Scene<Point2> {
-    let eye_coordinates = screen_parameter.get_eye_coordinates();
-    let view_angle = screen_parameter.get_view_angle();
+pub fn create_screen_scene(
+    scene: Scene<&Point>,
+    screen_parameter: &ScreenParameter,
+) -> Scene<ScreenVertex> {
+    let clip_matrix = get_camera_matrix(screen_parameter) * get_projection_matrix(screen_parameter);
     let (vsx, vsy, vcx, vcy) = screen_parameter.get_view_data();
 
-    let clipped_points = get_clipping_coordinates(eye_coordinates, view_angle, &scene);
+    let clipped_points = clip_scene(&get_clipping_coordinates(&clip_matrix, &scene));
     let screen_points = get_screen_coordinates(&clipped_points, vsx, vsy, vcx, vcy);
 
     return screen_points;
 }
 
+/// Orthographic front, top and side views of `scene` around `center`, laid
+/// out in a two by two grid filling the screen parameters' viewport. Each
+/// view sees `volume`, with `center` half way between its near and far
+/// planes. The front view looks along +y; the world is z-up.
+#[allow(dead_code)]
+pub fn create_multiview(
+    scene: &Scene<&Point>,
+    center: Vec3,
+    volume: &Orthographic,
+    arrangement: ViewArrangement,
+    screen_parameter: &ScreenParameter,
+) -> MultiView {
+    let (vsx, vsy, vcx, vcy) = screen_parameter.get_view_data();
+    let (hx, hy) = (vsx / 2.0, vsy / 2.0);
+    let distance = (volume.near + volume.far) / 2.0;
+    let projection = get_orthographic_matrix(volume);
+
+    let view = |direction: Vec3, up: Vec3, cx: f32, cy: f32| {
+        let eye = center - direction * distance;
+        let clip_matrix = get_look_at_matrix(eye, center, up) * projection;
+        let clipped = clip_scene(&get_clipping_coordinates(&clip_matrix, scene));
+        let lines = get_screen_coordinates(&clipped, hx, hy, cx, cy)
+            .lines
+            .iter()
+            .map(|line| Line::new(line.a.position, line.b.position))
+            .collect::<Vec<Line<Point2>>>();
+
+        return Scene {
+            num_lines: lines.len(),
+            lines,
+        };
+    };
+
+    let z_up = Vec3::new(0.0, 0.0, 1.0);
+    let (left, right, upper, lower) = (vcx - hx, vcx + hx, vcy + hy, vcy - hy);
+    let front = Vec3::new(0.0, 1.0, 0.0);
+    let down = Vec3::new(0.0, 0.0, -1.0);
+    let y_up = Vec3::new(0.0, 1.0, 0.0);
+
+    return match arrangement {
+        ViewArrangement::FirstAngle => MultiView {
+            front: view(front, z_up, left, upper),
+            top: view(down, y_up, left, lower),
+            side: view(Vec3::new(1.0, 0.0, 0.0), z_up, right, upper),
+        },
+        ViewArrangement::ThirdAngle => MultiView {
+            front: view(front, z_up, left, lower),
+            top: view(down, y_up, left, upper),
+            side: view(Vec3::new(-1.0, 0.0, 0.0), z_up, right, lower),
+        },
+    };
+}
+
+#[allow(dead_code)]
+impl MultiView {
+    /// All three views as one scene.
+    pub fn combined(self) -> Scene<Point2> {
+        let lines = [self.front, self.top, self.side]
+            .into_iter()
+            .flat_map(|view| view.lines)
+            .collect::<Vec<Line<Point2>>>();
+
+        return Scene {
+            num_lines: lines.len(),
+            lines,
+        };
+    }
+}
+
+pub fn create_scene(scene: Scene<&Point>, screen_parameter: &ScreenParameter) -> Scene<Point2> {
+    let screen_lines = create_screen_scene(scene, screen_parameter)
+        .lines
+        .iter()
+        .map(|line| Line::new(line.a.position, line.b.position))
+        .collect::<Vec<Line<Point2>>>();
+
+    return Scene {
+        num_lines: screen_lines.len(),
+        lines: screen_lines,
+    };
+}
+
 #[allow(dead_code)]
 fn print_original_points(scene: &Scene<&Point>) {
     println!("----------------ORIGINAL LINES----------------");
     scene.lines.iter().for_each(|line| {
-        println!("{} - {}", line.a.to_string(), line.b.to_string());
+        println!("{} - {}", line.a, line.b);
     })
 }
 #[allow(dead_code)]
-fn print_clipped_points(scene: &Scene<Point>) {
+fn print_clipped_points(scene: &Scene<Vec4>) {
     println!("----------------CLIPPED LINES----------------");
     scene.lines.iter().for_each(|line| {
-        println!("{} - {}", line.a.to_string(), line.b.to_string());
+        println!("{} - {}", line.a, line.b);
     })
 }
 #[allow(dead_code)]
 fn print_screen_points(scene: &Scene<Point2>) {
     println!("----------------SCREEN LINES----------------");
     scene.lines.iter().for_each(|line| {
-        println!("{} - {}", line.a.to_string(), line.b.to_string());
+        println!("{} - {}", line.a, line.b);
     })
 }
@@ -1,8 +1,13 @@
+#![allow(clippy::needless_return)]
+
 mod drawline;
 mod matrix;
+mod quaternion;
 mod solid;
+#[cfg(test)]
 mod tests;
 mod transformations;
+mod vector;
 
 use nannou::prelude::*;
 use solid::{GetLines, Scene, ScreenParameter};
@@ -55,9 +60,6 @@ fn view(app: &App, frame: Frame) {
     let draw = app.draw();
     draw.background().color(BURLYWOOD);
 
-    let win = app.window_rect();
-    let t = app.time;
-
     let scene = get_scene();
 
     scene.lines.iter().for_each(|line| {
@@ -1,71 +1,206 @@
+pub mod compare;
+pub mod decomposition;
+pub mod eigen;
+pub mod fixed;
+pub mod format;
+pub mod functions;
+pub mod io;
+pub mod multiply;
+pub mod ops;
+pub mod scalar;
+pub mod sparse;
+pub mod view;
+
 use core::fmt;
 use std::iter::zip;
-use std::usize;
 
-#[derive(Debug)]
-pub struct Matrix {
-    rows: usize,
-    cols: usize,
-    matrix: Vec<f32>,
+use format::{MatrixFormat, Precision};
+use scalar::{Field, Scalar};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MatrixError {
+    EmptyInput,
+    DataLength {
+        expected: usize,
+        found: usize,
+    },
+    RaggedRows {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    IndexOutOfBounds {
+        row: usize,
+        col: usize,
+        rows: usize,
+        cols: usize,
+    },
+    NotSquare {
+        rows: usize,
+        cols: usize,
+    },
+    DimensionMismatch {
+        left: (usize, usize),
+        right: (usize, usize),
+    },
+    Underdetermined {
+        rows: usize,
+        cols: usize,
+    },
+    Singular,
+    NotPositiveDefinite,
+    NotSymmetric,
+    NotConverged {
+        iterations: usize,
+    },
+    NoPrincipalLogarithm,
+    NotRotation,
+    NotAffine,
+    NonFinite,
 }
 
-impl fmt::Display for Matrix {
+impl fmt::Display for MatrixError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut result = String::from("\n");
-        let max_len = self
-            .matrix
-            .iter()
-            .map(|x| (*x).to_string().len())
-            .max()
-            .unwrap();
+        match self {
+            MatrixError::EmptyInput => return write!(f, "cannot build a matrix from empty input"),
+            MatrixError::DataLength { expected, found } => {
+                return write!(f, "expected {} elements, found {}", expected, found);
+            }
+            MatrixError::RaggedRows {
+                row,
+                expected,
+                found,
+            } => {
+                return write!(
+                    f,
+                    "row {} has {} columns, expected {}",
+                    row, found, expected
+                );
+            }
+            MatrixError::IndexOutOfBounds {
+                row,
+                col,
+                rows,
+                cols,
+            } => {
+                return write!(
+                    f,
+                    "index ({}, {}) out of bounds for {}x{} matrix",
+                    row, col, rows, cols
+                );
+            }
+            MatrixError::NotSquare { rows, cols } => {
+                return write!(f, "expected a square matrix, found {}x{}", rows, cols);
+            }
+            MatrixError::DimensionMismatch { left, right } => {
+                return write!(
+                    f,
+                    "dimension mismatch: {}x{} and {}x{}",
+                    left.0, left.1, right.0, right.1
+                );
+            }
+            MatrixError::Underdetermined { rows, cols } => {
+                return write!(f, "system is underdetermined: {}x{}", rows, cols);
+            }
+            MatrixError::Singular => return write!(f, "matrix is singular"),
+            MatrixError::NotPositiveDefinite => {
+                return write!(f, "matrix is not positive definite");
+            }
+            MatrixError::NotSymmetric => return write!(f, "matrix is not symmetric"),
+            MatrixError::NotConverged { iterations } => {
+                return write!(f, "did not converge after {} iterations", iterations);
+            }
+            MatrixError::NoPrincipalLogarithm => {
+                return write!(f, "matrix has no real principal logarithm");
+            }
+            MatrixError::NotRotation => return write!(f, "matrix is not a rotation"),
+            MatrixError::NotAffine => {
+                return write!(f, "matrix has a perspective part and is not affine");
+            }
+            MatrixError::NonFinite => return write!(f, "matrix has NaN or infinite elements"),
+        }
+    }
+}
+
+impl std::error::Error for MatrixError {}
 
-        let rows = self.matrix.as_slice().chunks(self.cols);
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericMatrix<T> {
+    rows: usize,
+    cols: usize,
+    matrix: Vec<T>,
+}
 
-        for row in rows {
-            result.push_str("[ ");
-            row.iter()
-                .map(|x| {
-                    let str = (*x).to_string();
-                    let padded_str = format!("{:>max_len$}", str);
+/// The `f32` matrix used by the rendering pipeline.
+pub type Matrix = GenericMatrix<f32>;
 
-                    return padded_str;
-                })
-                .for_each(|x| result.push_str(&format!("{} ", x)));
-            result.push_str("]\n");
-        }
+/// Prints one padded row per line. A precision such as `{:.3}` selects
+/// `Precision::Fixed`; see `format_with` for the other options.
+impl<T: Scalar> fmt::Display for GenericMatrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision().map_or(Precision::Full, Precision::Fixed);
+        let format = MatrixFormat::new().precision(precision);
 
-        return write!(f, "{}", result);
+        return f.write_str(&self.format_with(&format));
     }
 }
 
 #[allow(dead_code)]
-impl Matrix {
+impl<T: Scalar> GenericMatrix<T> {
     pub fn new(row: usize, col: usize) -> Self {
         Self {
             rows: row,
             cols: col,
-            matrix: vec![0.0; row * col],
+            matrix: vec![T::zero(); row * col],
         }
     }
 
-    pub fn from_vec(v: Vec<Vec<f32>>) -> Self {
-        Self {
-            rows: v.len(),
-            cols: v[0].len(),
-            matrix: v.into_iter().flatten().collect(),
+    pub fn identity(size: usize) -> Self {
+        let mut result = Self::new(size, size);
+        for i in 0..size {
+            result.put(i, i, T::one());
         }
+
+        return result;
     }
 
-    pub fn from_vec_i32(v: Vec<Vec<i32>>) -> Self {
+    pub fn from_array<const R: usize, const C: usize>(a: [[T; C]; R]) -> Self {
         Self {
+            rows: R,
+            cols: C,
+            matrix: a.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn from_vec(v: Vec<Vec<T>>) -> Result<Self, MatrixError> {
+        let cols = Self::check_rows(&v)?;
+
+        return Ok(Self {
             rows: v.len(),
-            cols: v[0].len(),
-            matrix: v
-                .into_iter()
-                .map(|x| x.iter().map(|y| *y as f32).collect::<Vec<f32>>())
-                .flatten()
-                .collect(),
+            cols,
+            matrix: v.into_iter().flatten().collect(),
+        });
+    }
+
+    /// Builds a matrix from elements laid out row after row.
+    pub fn from_row_major(rows: usize, cols: usize, data: Vec<T>) -> Result<Self, MatrixError> {
+        if data.len() != rows * cols {
+            return Err(MatrixError::DataLength {
+                expected: rows * cols,
+                found: data.len(),
+            });
         }
+
+        return Ok(Self {
+            rows,
+            cols,
+            matrix: data,
+        });
+    }
+
+    /// Elements in row-major order.
+    pub fn as_slice(&self) -> &[T] {
+        return &self.matrix;
     }
 
     pub fn num_rows(&self) -> usize {
@@ -76,42 +211,38 @@ impl Matrix {
         return self.cols;
     }
 
+    pub fn is_square(&self) -> bool {
+        return self.rows == self.cols;
+    }
+
     pub fn index(&self, row: usize, col: usize) -> usize {
         return self.cols * row + col;
     }
 
-    pub fn at(&self, row: usize, col: usize) -> f32 {
-        let val = self.matrix.get(self.index(row, col));
-
-        match val {
-            Some(x) => return *x,
-            None => return 0.0,
-        }
+    pub fn at(&self, row: usize, col: usize) -> Result<T, MatrixError> {
+        self.check_bounds(row, col)?;
+        return Ok(self.get(row, col));
     }
 
-    pub fn set(&mut self, row: usize, col: usize, value: f32) {
-        let index = self.index(row, col);
-
-        if let Some(num) = self.matrix.get_mut(index) {
-            *num = value;
-        } else {
-            panic!("Index out of Matrix bounds");
-        }
+    pub fn set(&mut self, row: usize, col: usize, value: T) -> Result<(), MatrixError> {
+        self.check_bounds(row, col)?;
+        self.put(row, col, value);
+        return Ok(());
     }
 
-    pub fn add(&mut self, value: f32) {
+    pub fn add(&mut self, value: T) {
         self.matrix.iter_mut().map(|x| *x += value).for_each(|_| {});
     }
 
-    pub fn subtract(&mut self, value: f32) {
+    pub fn subtract(&mut self, value: T) {
         self.matrix.iter_mut().map(|x| *x -= value).for_each(|_| {});
     }
 
-    pub fn multiply(&mut self, value: f32) {
+    pub fn multiply(&mut self, value: T) {
         self.matrix.iter_mut().map(|x| *x *= value).for_each(|_| {});
     }
 
-    pub fn equals(&self, m: &Matrix) -> bool {
+    pub fn equals(&self, m: &Self) -> bool {
         if self.rows != m.rows || self.cols != m.cols {
             return false;
         }
@@ -123,15 +254,13 @@ impl Matrix {
             .map(|(x, y)| (*x, *y))
             .collect();
 
-        return results.len() == 0;
+        return results.is_empty();
     }
 
-    pub fn matrix_add(&self, m: &Matrix) -> Option<Matrix> {
-        if self.rows != m.rows || self.cols != m.cols {
-            return None;
-        }
+    pub fn matrix_add(&self, m: &Self) -> Result<Self, MatrixError> {
+        self.check_same_shape(m)?;
 
-        let mut result = Matrix::new(self.rows, self.cols);
+        let mut result = Self::new(self.rows, self.cols);
         let result_iter = zip(self.matrix.iter(), m.matrix.iter());
 
         result_iter
@@ -143,67 +272,61 @@ impl Matrix {
                 }
             });
 
-        return Some(result);
+        return Ok(result);
+    }
+
+    pub fn matrix_subtract(&self, m: &Self) -> Result<Self, MatrixError> {
+        self.check_same_shape(m)?;
+
+        let matrix = zip(self.matrix.iter(), m.matrix.iter())
+            .map(|(x, y)| *x - *y)
+            .collect();
+
+        return Ok(Self {
+            rows: self.rows,
+            cols: self.cols,
+            matrix,
+        });
     }
 
-    pub fn matrix_multiply(&self, m: &Matrix) -> Option<Matrix> {
+    pub fn matrix_multiply(&self, m: &Self) -> Result<Self, MatrixError> {
         if self.cols != m.num_rows() {
-            return None;
+            return Err(self.mismatch(m));
         }
-        let mut result = Matrix::new(self.rows, m.num_cols());
-
-        let mut i = 0;
-        while i < result.num_rows() {
-            let mut j = 0;
-            while j < result.num_cols() {
-                let mut k = 0;
-                while k < m.num_rows() {
-                    let val = result.at(i, j) + self.at(i, k) * m.at(k, j);
-                    result.set(i, j, val);
-                    k += 1;
-                }
-                j += 1;
-            }
-            i += 1;
-        }
-        return Some(result);
+
+        let matrix = self
+            .rows()
+            .flat_map(|row| m.columns().map(move |col| Self::dot_product(row, col)))
+            .collect();
+
+        return Ok(Self {
+            rows: self.rows,
+            cols: m.cols,
+            matrix,
+        });
     }
 
-    pub fn matrix_multiply2(&self, m: &Matrix) -> Option<Matrix> {
+    /// Transposes `m` once so that every dot product reads two contiguous
+    /// rows.
+    pub fn matrix_multiply2(&self, m: &Self) -> Result<Self, MatrixError> {
         if self.cols != m.num_rows() {
-            return None;
+            return Err(self.mismatch(m));
         }
 
-        let mut result = Matrix::new(self.rows, m.num_cols());
-
-        let rows = result.num_rows();
-        let cols = result.num_cols();
-        result.matrix.iter_mut().enumerate().for_each(|(i, num)| {
-            let coords = Matrix::get_coords(i, rows, cols);
-
-            match coords {
-                None => return (),
-                Some((x, y)) => {
-                    let row = self
-                        .matrix
-                        .as_slice()
-                        .chunks(self.cols)
-                        .nth(x)
-                        .unwrap_or(&[0.0]);
-                    let col = m
-                        .matrix
-                        .iter()
-                        .enumerate()
-                        .filter(|(i, _num)| i % m.cols == y)
-                        .map(|(_j, num)| *num)
-                        .collect::<Vec<f32>>();
-
-                    *num = Matrix::dot_product(row, col.as_slice());
-                }
-            };
-        });
+        let m_t = m.transpose();
+        let matrix = self
+            .rows()
+            .flat_map(|row| {
+                m_t.rows()
+                    .map(move |col| Self::dot_product(row, col.iter().copied()))
+            })
+            .collect();
 
-        return Some(result);
+        return Ok(Self {
+            rows: self.rows,
+            cols: m.cols,
+            matrix,
+        });
     }
 
     pub fn get_coords(index: usize, rows: usize, cols: usize) -> Option<(usize, usize)> {
@@ -213,16 +336,252 @@ impl Matrix {
         return Some((index / cols, index % cols));
     }
 
-    pub fn print_matrix(m: Option<Matrix>) {
+    pub fn print_matrix(m: Result<Self, MatrixError>) {
         match m {
-            Some(mat) => println!("{}", mat.to_string()),
-            None => println!("{}", String::from("Invalid operation")),
+            Ok(mat) => println!("{}", mat),
+            Err(e) => println!("Invalid operation: {}", e),
+        }
+    }
+
+    pub fn map<U: Scalar>(&self, mut f: impl FnMut(T) -> U) -> GenericMatrix<U> {
+        return GenericMatrix {
+            rows: self.rows,
+            cols: self.cols,
+            matrix: self.matrix.iter().map(|x| f(*x)).collect(),
+        };
+    }
+
+    /// Unchecked read for callers that have already validated the index.
+    fn get(&self, row: usize, col: usize) -> T {
+        return self.matrix[self.index(row, col)];
+    }
+
+    /// Unchecked write for callers that have already validated the index.
+    fn put(&mut self, row: usize, col: usize, value: T) {
+        let index = self.index(row, col);
+        self.matrix[index] = value;
+    }
+
+    fn check_bounds(&self, row: usize, col: usize) -> Result<(), MatrixError> {
+        if row >= self.rows || col >= self.cols {
+            return Err(MatrixError::IndexOutOfBounds {
+                row,
+                col,
+                rows: self.rows,
+                cols: self.cols,
+            });
+        }
+        return Ok(());
+    }
+
+    /// Validates nested input and returns its column count.
+    fn check_rows<E>(v: &[Vec<E>]) -> Result<usize, MatrixError> {
+        let cols = match v.first() {
+            Some(row) if !row.is_empty() => row.len(),
+            _ => return Err(MatrixError::EmptyInput),
+        };
+
+        if let Some((row, found)) = v
+            .iter()
+            .map(|x| x.len())
+            .enumerate()
+            .find(|(_, len)| *len != cols)
+        {
+            return Err(MatrixError::RaggedRows {
+                row,
+                expected: cols,
+                found,
+            });
+        }
+
+        return Ok(cols);
+    }
+
+    fn check_same_shape(&self, m: &Self) -> Result<(), MatrixError> {
+        if self.rows != m.rows || self.cols != m.cols {
+            return Err(self.mismatch(m));
+        }
+        return Ok(());
+    }
+
+    fn mismatch(&self, m: &Self) -> MatrixError {
+        return MatrixError::DimensionMismatch {
+            left: (self.rows, self.cols),
+            right: (m.rows, m.cols),
+        };
+    }
+
+    fn check_square(&self) -> Result<(), MatrixError> {
+        if !self.is_square() {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows,
+                cols: self.cols,
+            });
+        }
+        return Ok(());
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+        for j in 0..self.cols {
+            let (i, k) = (self.index(a, j), self.index(b, j));
+            self.matrix.swap(i, k);
         }
     }
 
-    fn dot_product(row: &[f32], col: &[f32]) -> f32 {
+    /// Sums in order starting from zero, which every multiply relies on to
+    /// produce bit-identical results.
+    fn dot_product(row: &[T], col: impl Iterator<Item = T>) -> T {
         let result_iter = zip(row, col);
 
-        return result_iter.map(|(x, y)| (*x) * (*y)).sum();
+        return result_iter.fold(T::zero(), |sum, (x, y)| sum + *x * y);
+    }
+}
+
+impl Matrix {
+    pub fn from_vec_i32(v: Vec<Vec<i32>>) -> Result<Self, MatrixError> {
+        let cols = Self::check_rows(&v)?;
+
+        return Ok(Self {
+            rows: v.len(),
+            cols,
+            matrix: v
+                .into_iter()
+                .flat_map(|x| x.iter().map(|y| *y as f32).collect::<Vec<f32>>())
+                .collect(),
+        });
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Field> GenericMatrix<T> {
+    /// Determinant by Gaussian elimination with partial pivoting.
+    pub fn determinant(&self) -> Result<T, MatrixError> {
+        self.check_square()?;
+
+        let n = self.rows;
+        let mut m = self.clone();
+        let mut det = T::one();
+
+        for k in 0..n {
+            let pivot = m.pivot_row(k, k);
+            if m.get(pivot, k) == T::zero() {
+                return Ok(T::zero());
+            }
+            if pivot != k {
+                m.swap_rows(pivot, k);
+                det = -det;
+            }
+
+            let p = m.get(k, k);
+            det *= p;
+
+            for i in k + 1..n {
+                let factor = m.get(i, k) / p;
+                for j in k..n {
+                    let val = m.get(i, j) - factor * m.get(k, j);
+                    m.put(i, j, val);
+                }
+            }
+        }
+
+        return Ok(det);
+    }
+
+    /// Number of linearly independent rows, found by reducing to row echelon
+    /// form. Pivots below `pivot_tolerance` are treated as zero.
+    pub fn rank(&self) -> usize {
+        let mut m = self.clone();
+        let tolerance = self.pivot_tolerance();
+        let mut rank = 0;
+
+        for col in 0..self.cols {
+            if rank == self.rows {
+                break;
+            }
+
+            let pivot = m.pivot_row(rank, col);
+            if m.get(pivot, col).abs() <= tolerance {
+                continue;
+            }
+            m.swap_rows(pivot, rank);
+
+            let p = m.get(rank, col);
+            for i in rank + 1..self.rows {
+                let factor = m.get(i, col) / p;
+                for j in col..self.cols {
+                    let val = m.get(i, j) - factor * m.get(rank, j);
+                    m.put(i, j, val);
+                }
+            }
+            rank += 1;
+        }
+
+        return rank;
+    }
+
+    /// Inverse by Gauss-Jordan elimination with partial pivoting.
+    pub fn inverse(&self) -> Result<Self, MatrixError> {
+        self.check_square()?;
+
+        let n = self.rows;
+        let tolerance = self.pivot_tolerance();
+        let mut m = self.clone();
+        let mut inv = Self::identity(n);
+
+        for k in 0..n {
+            let pivot = m.pivot_row(k, k);
+            if m.get(pivot, k).abs() <= tolerance {
+                return Err(MatrixError::Singular);
+            }
+            m.swap_rows(pivot, k);
+            inv.swap_rows(pivot, k);
+
+            let p = m.get(k, k);
+            for j in 0..n {
+                m.put(k, j, m.get(k, j) / p);
+                inv.put(k, j, inv.get(k, j) / p);
+            }
+
+            for i in 0..n {
+                if i == k {
+                    continue;
+                }
+                let factor = m.get(i, k);
+                if factor == T::zero() {
+                    continue;
+                }
+                for j in 0..n {
+                    m.put(i, j, m.get(i, j) - factor * m.get(k, j));
+                    inv.put(i, j, inv.get(i, j) - factor * inv.get(k, j));
+                }
+            }
+        }
+
+        return Ok(inv);
+    }
+
+    /// Row at or below `start` with the largest absolute value in `col`.
+    fn pivot_row(&self, start: usize, col: usize) -> usize {
+        let mut pivot = start;
+        for i in start + 1..self.rows {
+            if self.get(i, col).abs() > self.get(pivot, col).abs() {
+                pivot = i;
+            }
+        }
+        return pivot;
+    }
+
+    /// Scale-aware threshold below which a pivot counts as zero.
+    fn pivot_tolerance(&self) -> T {
+        let max_abs = self.matrix.iter().fold(T::zero(), |acc, x| {
+            if x.abs() > acc {
+                return x.abs();
+            }
+            return acc;
+        });
+        return T::epsilon() * T::from_usize(self.rows.max(self.cols)) * max_abs;
     }
 }
@@ -0,0 +1,153 @@
+use core::fmt;
+
+use crate::matrix::fixed::SMatrix;
+use crate::matrix::scalar::Real;
+use crate::matrix::{GenericMatrix, MatrixError};
+
+/// How close two elements must be to count as equal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tolerance<T> {
+    /// `|a - b| <= tolerance`. Use this for values near zero.
+    Absolute(T),
+    /// `|a - b| <= tolerance * max(|a|, |b|)`.
+    Relative(T),
+    /// At most this many representable values apart.
+    Ulps(u64),
+}
+
+/// The element where two matrices differ the most, by absolute difference.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElementDifference<T> {
+    pub row: usize,
+    pub col: usize,
+    pub left: T,
+    pub right: T,
+    pub difference: T,
+    pub ulps: u64,
+}
+
+impl<T: Real> Tolerance<T> {
+    pub fn accepts(&self, a: T, b: T) -> bool {
+        match *self {
+            Tolerance::Absolute(tolerance) => return (a - b).abs() <= tolerance,
+            Tolerance::Relative(tolerance) => {
+                let scale = if a.abs() > b.abs() { a.abs() } else { b.abs() };
+                return (a - b).abs() <= tolerance * scale;
+            }
+            Tolerance::Ulps(ulps) => return a.ulps_between(b) <= ulps,
+        }
+    }
+}
+
+impl<T: Real> fmt::Display for ElementDifference<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(
+            f,
+            "largest difference at ({}, {}): {} vs {} (by {}, {} ulps)",
+            self.row, self.col, self.left, self.right, self.difference, self.ulps
+        );
+    }
+}
+
+impl<T: Real> GenericMatrix<T> {
+    /// Element-wise comparison under `tolerance`. Matrices of different shape
+    /// are never equal.
+    pub fn approx_eq(&self, m: &Self, tolerance: Tolerance<T>) -> bool {
+        if self.check_same_shape(m).is_err() {
+            return false;
+        }
+
+        return self
+            .matrix
+            .iter()
+            .zip(m.matrix.iter())
+            .all(|(a, b)| tolerance.accepts(*a, *b));
+    }
+
+    /// Finds the element with the largest absolute difference. A NaN on
+    /// either side counts as the largest possible difference.
+    pub fn max_difference(&self, m: &Self) -> Result<ElementDifference<T>, MatrixError> {
+        self.check_same_shape(m)?;
+
+        let mut worst: Option<ElementDifference<T>> = None;
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                let (left, right) = (self.get(i, j), m.get(i, j));
+                let ulps = left.ulps_between(right);
+                let difference = (left - right).abs();
+
+                let worse = match &worst {
+                    None => true,
+                    Some(w) if ulps == u64::MAX => w.ulps != u64::MAX,
+                    Some(w) => w.ulps != u64::MAX && difference > w.difference,
+                };
+                if worse {
+                    worst = Some(ElementDifference {
+                        row: i,
+                        col: j,
+                        left,
+                        right,
+                        difference,
+                        ulps,
+                    });
+                }
+            }
+        }
+
+        return worst.ok_or(MatrixError::EmptyInput);
+    }
+
+    /// 2-norm condition number, the ratio of the largest to the smallest
+    /// singular value. Infinite for a rank-deficient matrix.
+    pub fn condition_number(&self) -> Result<T, MatrixError> {
+        if self.matrix.is_empty() {
+            return Err(MatrixError::EmptyInput);
+        }
+
+        let svd = self.svd()?;
+        let values = svd.singular_values();
+        let (largest, smallest) = (values[0], values[values.len() - 1]);
+
+        if smallest == T::zero() {
+            return Ok(T::from_f64(f64::INFINITY));
+        }
+        return Ok(largest / smallest);
+    }
+
+    /// Frobenius norm of `A^T * A - I`: zero for a rotation or reflection,
+    /// growing as rounding drift skews or stretches the axes.
+    pub fn orthogonality_error(&self) -> Result<T, MatrixError> {
+        self.check_square()?;
+
+        let n = self.rows;
+        let mut sum = T::zero();
+        for i in 0..n {
+            for j in 0..n {
+                let dot: T = (0..n).map(|k| self.get(k, i) * self.get(k, j)).sum();
+                let expected = if i == j { T::one() } else { T::zero() };
+                sum += (dot - expected) * (dot - expected);
+            }
+        }
+        return Ok(sum.sqrt());
+    }
+}
+
+impl<const R: usize, const C: usize, T: Real> SMatrix<R, C, T> {
+    pub fn approx_eq(&self, m: &Self, tolerance: Tolerance<T>) -> bool {
+        return self
+            .as_array()
+            .iter()
+            .flatten()
+            .zip(m.as_array().iter().flatten())
+            .all(|(a, b)| tolerance.accepts(*a, *b));
+    }
+}
+
+impl<const N: usize, T: Real> SMatrix<N, N, T> {
+    pub fn orthogonality_error(&self) -> T {
+        return self
+            .to_matrix()
+            .orthogonality_error()
+            .expect("SMatrix<N, N> is square");
+    }
+}
@@ -0,0 +1,305 @@
+use crate::matrix::scalar::{Field, Real};
+use crate::matrix::{GenericMatrix, MatrixError};
+
+/// LU factorisation with partial pivoting, `P * A = L * U`.
+///
+/// `L` (unit diagonal) and `U` are packed into one matrix.
+#[derive(Debug, Clone)]
+pub struct Lu<T> {
+    lu: GenericMatrix<T>,
+    permutation: Vec<usize>,
+    sign: T,
+    tolerance: T,
+}
+
+/// Householder QR factorisation, `A = Q * R`, with `Q` orthogonal (m x m)
+/// and `R` upper triangular (m x n).
+#[derive(Debug, Clone)]
+pub struct Qr<T> {
+    q: GenericMatrix<T>,
+    r: GenericMatrix<T>,
+    tolerance: T,
+}
+
+/// Cholesky factorisation of a symmetric positive definite matrix,
+/// `A = L * L^T`. Only the lower triangle of `A` is read.
+#[derive(Debug, Clone)]
+pub struct Cholesky<T> {
+    l: GenericMatrix<T>,
+}
+
+impl<T: Field> Lu<T> {
+    pub fn l(&self) -> GenericMatrix<T> {
+        let n = self.lu.rows;
+        let mut l = GenericMatrix::identity(n);
+        for i in 0..n {
+            for j in 0..i {
+                l.put(i, j, self.lu.get(i, j));
+            }
+        }
+        return l;
+    }
+
+    pub fn u(&self) -> GenericMatrix<T> {
+        let n = self.lu.rows;
+        let mut u = GenericMatrix::new(n, n);
+        for i in 0..n {
+            for j in i..n {
+                u.put(i, j, self.lu.get(i, j));
+            }
+        }
+        return u;
+    }
+
+    pub fn p(&self) -> GenericMatrix<T> {
+        let n = self.lu.rows;
+        let mut p = GenericMatrix::new(n, n);
+        self.permutation
+            .iter()
+            .enumerate()
+            .for_each(|(i, j)| p.put(i, *j, T::one()));
+        return p;
+    }
+
+    pub fn determinant(&self) -> T {
+        let n = self.lu.rows;
+        return (0..n).fold(self.sign, |acc, i| acc * self.lu.get(i, i));
+    }
+
+    /// Solves `A * X = B` for every column of `B`.
+    pub fn solve(&self, b: &GenericMatrix<T>) -> Result<GenericMatrix<T>, MatrixError> {
+        let n = self.lu.rows;
+        check_rhs((n, n), b)?;
+
+        if (0..n).any(|i| self.lu.get(i, i).abs() <= self.tolerance) {
+            return Err(MatrixError::Singular);
+        }
+
+        let mut x = GenericMatrix::new(n, b.cols);
+        for c in 0..b.cols {
+            // Forward substitution with the permuted right-hand side.
+            let mut y = vec![T::zero(); n];
+            for i in 0..n {
+                let sum: T = (0..i).map(|k| self.lu.get(i, k) * y[k]).sum();
+                y[i] = b.get(self.permutation[i], c) - sum;
+            }
+
+            for i in (0..n).rev() {
+                let sum: T = (i + 1..n).map(|k| self.lu.get(i, k) * x.get(k, c)).sum();
+                x.put(i, c, (y[i] - sum) / self.lu.get(i, i));
+            }
+        }
+
+        return Ok(x);
+    }
+}
+
+impl<T: Real> Qr<T> {
+    pub fn q(&self) -> &GenericMatrix<T> {
+        return &self.q;
+    }
+
+    pub fn r(&self) -> &GenericMatrix<T> {
+        return &self.r;
+    }
+
+    /// Minimises `|A * X - B|` column by column. Needs at least as many rows
+    /// as columns and full column rank.
+    pub fn solve_least_squares(
+        &self,
+        b: &GenericMatrix<T>,
+    ) -> Result<GenericMatrix<T>, MatrixError> {
+        let (m, n) = (self.r.rows, self.r.cols);
+        check_rhs((m, n), b)?;
+
+        if m < n {
+            return Err(MatrixError::Underdetermined { rows: m, cols: n });
+        }
+        if (0..n).any(|i| self.r.get(i, i).abs() <= self.tolerance) {
+            return Err(MatrixError::Singular);
+        }
+
+        let mut x = GenericMatrix::new(n, b.cols);
+        for c in 0..b.cols {
+            // Only the first n entries of Q^T * b reach the solution.
+            let qtb: Vec<T> = (0..n)
+                .map(|i| (0..m).map(|k| self.q.get(k, i) * b.get(k, c)).sum())
+                .collect();
+
+            for i in (0..n).rev() {
+                let sum: T = (i + 1..n).map(|k| self.r.get(i, k) * x.get(k, c)).sum();
+                x.put(i, c, (qtb[i] - sum) / self.r.get(i, i));
+            }
+        }
+
+        return Ok(x);
+    }
+}
+
+impl<T: Real> Cholesky<T> {
+    pub fn l(&self) -> &GenericMatrix<T> {
+        return &self.l;
+    }
+
+    /// Solves `A * X = B` through `L * Y = B` then `L^T * X = Y`.
+    pub fn solve(&self, b: &GenericMatrix<T>) -> Result<GenericMatrix<T>, MatrixError> {
+        let n = self.l.rows;
+        check_rhs((n, n), b)?;
+
+        let mut x = GenericMatrix::new(n, b.cols);
+        for c in 0..b.cols {
+            let mut y = vec![T::zero(); n];
+            for i in 0..n {
+                let sum: T = (0..i).map(|k| self.l.get(i, k) * y[k]).sum();
+                y[i] = (b.get(i, c) - sum) / self.l.get(i, i);
+            }
+
+            for i in (0..n).rev() {
+                let sum: T = (i + 1..n).map(|k| self.l.get(k, i) * x.get(k, c)).sum();
+                x.put(i, c, (y[i] - sum) / self.l.get(i, i));
+            }
+        }
+
+        return Ok(x);
+    }
+}
+
+impl<T: Field> GenericMatrix<T> {
+    pub fn lu(&self) -> Result<Lu<T>, MatrixError> {
+        self.check_square()?;
+
+        let n = self.rows;
+        let mut lu = self.clone();
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut sign = T::one();
+
+        for k in 0..n {
+            let pivot = lu.pivot_row(k, k);
+            if pivot != k {
+                lu.swap_rows(pivot, k);
+                permutation.swap(pivot, k);
+                sign = -sign;
+            }
+
+            let p = lu.get(k, k);
+            if p == T::zero() {
+                continue;
+            }
+
+            for i in k + 1..n {
+                let factor = lu.get(i, k) / p;
+                lu.put(i, k, factor);
+                for j in k + 1..n {
+                    let val = lu.get(i, j) - factor * lu.get(k, j);
+                    lu.put(i, j, val);
+                }
+            }
+        }
+
+        return Ok(Lu {
+            lu,
+            permutation,
+            sign,
+            tolerance: self.pivot_tolerance(),
+        });
+    }
+
+    /// Solves the square system `self * X = b` using LU.
+    pub fn solve(&self, b: &GenericMatrix<T>) -> Result<GenericMatrix<T>, MatrixError> {
+        return self.lu()?.solve(b);
+    }
+}
+
+impl<T: Real> GenericMatrix<T> {
+    pub fn qr(&self) -> Qr<T> {
+        let (m, n) = (self.rows, self.cols);
+        let mut r = self.clone();
+        let mut q = GenericMatrix::identity(m);
+
+        for k in 0..n.min(m.saturating_sub(1)) {
+            let norm = (k..m).map(|i| r.get(i, k) * r.get(i, k)).sum::<T>().sqrt();
+            if norm == T::zero() {
+                continue;
+            }
+
+            // Reflect towards the sign that avoids cancellation.
+            let alpha = if r.get(k, k) > T::zero() { -norm } else { norm };
+            let mut v: Vec<T> = (k..m).map(|i| r.get(i, k)).collect();
+            v[0] -= alpha;
+            let v_norm_sq: T = v.iter().map(|x| *x * *x).sum();
+            if v_norm_sq == T::zero() {
+                continue;
+            }
+
+            // R = H * R
+            for j in 0..n {
+                let dot: T = (k..m).map(|i| v[i - k] * r.get(i, j)).sum();
+                let factor = T::from_usize(2) * dot / v_norm_sq;
+                for i in k..m {
+                    r.put(i, j, r.get(i, j) - factor * v[i - k]);
+                }
+            }
+
+            // Q = Q * H
+            for i in 0..m {
+                let dot: T = (k..m).map(|j| q.get(i, j) * v[j - k]).sum();
+                let factor = T::from_usize(2) * dot / v_norm_sq;
+                for j in k..m {
+                    q.put(i, j, q.get(i, j) - factor * v[j - k]);
+                }
+            }
+
+            for i in k + 1..m {
+                r.put(i, k, T::zero());
+            }
+        }
+
+        return Qr {
+            q,
+            r,
+            tolerance: self.pivot_tolerance(),
+        };
+    }
+
+    pub fn cholesky(&self) -> Result<Cholesky<T>, MatrixError> {
+        self.check_square()?;
+
+        let n = self.rows;
+        let mut l = GenericMatrix::new(n, n);
+
+        for j in 0..n {
+            let sum: T = (0..j).map(|k| l.get(j, k) * l.get(j, k)).sum();
+            let diag = self.get(j, j) - sum;
+            if diag <= T::zero() {
+                return Err(MatrixError::NotPositiveDefinite);
+            }
+            let diag = diag.sqrt();
+            l.put(j, j, diag);
+
+            for i in j + 1..n {
+                let sum: T = (0..j).map(|k| l.get(i, k) * l.get(j, k)).sum();
+                l.put(i, j, (self.get(i, j) - sum) / diag);
+            }
+        }
+
+        return Ok(Cholesky { l });
+    }
+
+    /// Least-squares solution of `self * X = b` using QR.
+    pub fn solve_least_squares(
+        &self,
+        b: &GenericMatrix<T>,
+    ) -> Result<GenericMatrix<T>, MatrixError> {
+        return self.qr().solve_least_squares(b);
+    }
+}
+
+fn check_rhs<T>(shape: (usize, usize), b: &GenericMatrix<T>) -> Result<(), MatrixError> {
+    if b.rows != shape.0 {
+        return Err(MatrixError::DimensionMismatch {
+            left: shape,
+            right: (b.rows, b.cols),
+        });
+    }
+    return Ok(());
+}
@@ -0,0 +1,272 @@
+use std::cmp::Ordering;
+
+use crate::matrix::scalar::Real;
+use crate::matrix::{GenericMatrix, MatrixError};
+
+const MAX_SWEEPS: usize = 64;
+
+/// Eigen-decomposition of a symmetric matrix, `A = V * diag(values) * V^T`.
+///
+/// Eigenvalues are sorted in descending order and column `i` of `vectors` is
+/// the unit eigenvector for `values[i]`.
+#[derive(Debug, Clone)]
+pub struct SymmetricEigen<T> {
+    values: Vec<T>,
+    vectors: GenericMatrix<T>,
+}
+
+/// Thin singular value decomposition, `A = U * diag(singular_values) * V^T`.
+///
+/// For an m x n matrix with `k = min(m, n)`, `U` is m x k and `V` is n x k,
+/// both with orthonormal columns. Singular values are sorted in descending
+/// order.
+#[derive(Debug, Clone)]
+pub struct Svd<T> {
+    u: GenericMatrix<T>,
+    singular_values: Vec<T>,
+    v: GenericMatrix<T>,
+}
+
+impl<T: Real> SymmetricEigen<T> {
+    pub fn values(&self) -> &[T] {
+        return &self.values;
+    }
+
+    pub fn vectors(&self) -> &GenericMatrix<T> {
+        return &self.vectors;
+    }
+}
+
+impl<T: Real> Svd<T> {
+    pub fn u(&self) -> &GenericMatrix<T> {
+        return &self.u;
+    }
+
+    pub fn singular_values(&self) -> &[T] {
+        return &self.singular_values;
+    }
+
+    pub fn v(&self) -> &GenericMatrix<T> {
+        return &self.v;
+    }
+}
+
+impl<T: Real> GenericMatrix<T> {
+    /// Cyclic Jacobi eigen-solver. Fails if the matrix is not square or not
+    /// symmetric within rounding.
+    pub fn symmetric_eigen(&self) -> Result<SymmetricEigen<T>, MatrixError> {
+        self.check_square()?;
+
+        let n = self.rows;
+        let tolerance = self.pivot_tolerance();
+        for i in 0..n {
+            for j in i + 1..n {
+                if (self.get(i, j) - self.get(j, i)).abs() > tolerance {
+                    return Err(MatrixError::NotSymmetric);
+                }
+            }
+        }
+
+        let mut a = self.clone();
+        let mut v = GenericMatrix::identity(n);
+
+        let mut converged = off_diagonal_norm(&a) <= tolerance;
+        for _ in 0..MAX_SWEEPS {
+            if converged {
+                break;
+            }
+
+            for p in 0..n {
+                for q in p + 1..n {
+                    let apq = a.get(p, q);
+                    if apq == T::zero() {
+                        continue;
+                    }
+
+                    let theta = (a.get(q, q) - a.get(p, p)) / (T::from_usize(2) * apq);
+                    let t = theta.signum() / (theta.abs() + (theta * theta + T::one()).sqrt());
+                    let c = T::one() / (t * t + T::one()).sqrt();
+                    let s = t * c;
+
+                    rotate_columns(&mut a, p, q, c, s);
+                    rotate_rows(&mut a, p, q, c, s);
+                    rotate_columns(&mut v, p, q, c, s);
+                }
+            }
+            converged = off_diagonal_norm(&a) <= tolerance;
+        }
+        if !converged {
+            return Err(MatrixError::NotConverged {
+                iterations: MAX_SWEEPS,
+            });
+        }
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|i, j| {
+            a.get(*j, *j)
+                .partial_cmp(&a.get(*i, *i))
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let values = order.iter().map(|i| a.get(*i, *i)).collect();
+        let vectors = select_columns(&v, &order);
+
+        return Ok(SymmetricEigen { values, vectors });
+    }
+
+    /// One-sided Jacobi SVD. Columns of `U` belonging to zero singular values
+    /// are completed so that `U` stays orthonormal. Fails with `NotConverged`
+    /// if the columns are still not orthogonal after the last sweep.
+    pub fn svd(&self) -> Result<Svd<T>, MatrixError> {
+        if self.rows < self.cols {
+            let svd = self.transpose().svd()?;
+            return Ok(Svd {
+                u: svd.v,
+                singular_values: svd.singular_values,
+                v: svd.u,
+            });
+        }
+
+        let (m, n) = (self.rows, self.cols);
+        let mut u = self.clone();
+        let mut v = GenericMatrix::identity(n);
+
+        // Columns this small are rounding noise, as with the cutoff below,
+        // so they count as orthogonal to every other column.
+        let size: T = self.matrix.iter().map(|x| *x * *x).sum();
+        let negligible = T::epsilon() * T::epsilon() * size;
+
+        let mut converged = false;
+        for _ in 0..MAX_SWEEPS {
+            let mut rotated = false;
+
+            for i in 0..n {
+                for j in i + 1..n {
+                    let alpha: T = (0..m).map(|k| u.get(k, i) * u.get(k, i)).sum();
+                    let beta: T = (0..m).map(|k| u.get(k, j) * u.get(k, j)).sum();
+                    let gamma: T = (0..m).map(|k| u.get(k, i) * u.get(k, j)).sum();
+
+                    if gamma.abs() <= T::epsilon() * (alpha * beta).sqrt()
+                        || alpha <= negligible
+                        || beta <= negligible
+                    {
+                        continue;
+                    }
+                    rotated = true;
+
+                    let zeta = (beta - alpha) / (T::from_usize(2) * gamma);
+                    let t = zeta.signum() / (zeta.abs() + (zeta * zeta + T::one()).sqrt());
+                    let c = T::one() / (t * t + T::one()).sqrt();
+                    let s = t * c;
+
+                    rotate_columns(&mut u, i, j, c, s);
+                    rotate_columns(&mut v, i, j, c, s);
+                }
+            }
+
+            if !rotated {
+                converged = true;
+                break;
+            }
+        }
+        if !converged {
+            return Err(MatrixError::NotConverged {
+                iterations: MAX_SWEEPS,
+            });
+        }
+
+        let norms: Vec<T> = (0..n)
+            .map(|j| (0..m).map(|k| u.get(k, j) * u.get(k, j)).sum::<T>().sqrt())
+            .collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|i, j| norms[*j].partial_cmp(&norms[*i]).unwrap_or(Ordering::Equal));
+
+        let singular_values: Vec<T> = order.iter().map(|i| norms[*i]).collect();
+        let mut u = select_columns(&u, &order);
+        let v = select_columns(&v, &order);
+
+        let cutoff =
+            singular_values.first().copied().unwrap_or(T::zero()) * T::epsilon() * T::from_usize(m);
+        for (j, sigma) in singular_values.iter().enumerate() {
+            if *sigma > cutoff {
+                for k in 0..m {
+                    u.put(k, j, u.get(k, j) / *sigma);
+                }
+            } else {
+                complete_column(&mut u, j);
+            }
+        }
+
+        return Ok(Svd {
+            u,
+            singular_values,
+            v,
+        });
+    }
+}
+
+/// Frobenius norm of everything off the diagonal.
+fn off_diagonal_norm<T: Real>(a: &GenericMatrix<T>) -> T {
+    let n = a.rows;
+    return (0..n)
+        .flat_map(|i| (0..n).filter(move |j| *j != i).map(move |j| (i, j)))
+        .map(|(i, j)| a.get(i, j) * a.get(i, j))
+        .sum::<T>()
+        .sqrt();
+}
+
+/// `M = M * J` for the Givens rotation `J` acting on columns `p` and `q`.
+fn rotate_columns<T: Real>(m: &mut GenericMatrix<T>, p: usize, q: usize, c: T, s: T) {
+    for k in 0..m.rows {
+        let (mp, mq) = (m.get(k, p), m.get(k, q));
+        m.put(k, p, c * mp - s * mq);
+        m.put(k, q, s * mp + c * mq);
+    }
+}
+
+/// `M = J^T * M` for the Givens rotation `J` acting on rows `p` and `q`.
+fn rotate_rows<T: Real>(m: &mut GenericMatrix<T>, p: usize, q: usize, c: T, s: T) {
+    for k in 0..m.cols {
+        let (mp, mq) = (m.get(p, k), m.get(q, k));
+        m.put(p, k, c * mp - s * mq);
+        m.put(q, k, s * mp + c * mq);
+    }
+}
+
+fn select_columns<T: Real>(m: &GenericMatrix<T>, order: &[usize]) -> GenericMatrix<T> {
+    let mut result = GenericMatrix::new(m.rows, order.len());
+    for (j, col) in order.iter().enumerate() {
+        for i in 0..m.rows {
+            result.put(i, j, m.get(i, *col));
+        }
+    }
+    return result;
+}
+
+/// Replaces column `col` with a unit vector orthogonal to every column
+/// before it, using the standard basis vector that survives Gram-Schmidt best.
+fn complete_column<T: Real>(u: &mut GenericMatrix<T>, col: usize) {
+    let m = u.rows;
+
+    let best = (0..m)
+        .map(|e| {
+            let mut candidate: Vec<T> = (0..m)
+                .map(|k| if k == e { T::one() } else { T::zero() })
+                .collect();
+            for j in 0..col {
+                let dot: T = (0..m).map(|k| u.get(k, j) * candidate[k]).sum();
+                (0..m).for_each(|k| candidate[k] -= dot * u.get(k, j));
+            }
+            return candidate;
+        })
+        .max_by(|a, b| norm(a).partial_cmp(&norm(b)).unwrap_or(Ordering::Equal));
+
+    if let Some(candidate) = best {
+        let length = norm(&candidate);
+        (0..m).for_each(|k| u.put(k, col, candidate[k] / length));
+    }
+}
+
+fn norm<T: Real>(v: &[T]) -> T {
+    return v.iter().map(|x| *x * *x).sum::<T>().sqrt();
+}
@@ -0,0 +1,185 @@
+use core::fmt;
+use std::ops::{Add, Index, IndexMut, Mul, Neg, Sub};
+
+use crate::matrix::scalar::Scalar;
+use crate::matrix::{GenericMatrix, MatrixError};
+
+/// Matrix with its shape fixed at compile time and stored inline.
+///
+/// Multiplying an `SMatrix<R, C, T>` by anything other than an `SMatrix<C, K, T>`
+/// is a type error, so the shape checks `Matrix` does at runtime are not
+/// needed here.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SMatrix<const R: usize, const C: usize, T = f32> {
+    matrix: [[T; C]; R],
+}
+
+/// The `f32` 4x4 matrix used for homogeneous transforms.
+pub type Matrix4 = SMatrix<4, 4>;
+
+impl<const R: usize, const C: usize, T: Scalar> SMatrix<R, C, T> {
+    pub fn new() -> Self {
+        Self {
+            matrix: [[T::zero(); C]; R],
+        }
+    }
+
+    pub fn from_array(matrix: [[T; C]; R]) -> Self {
+        Self { matrix }
+    }
+
+    pub fn as_array(&self) -> &[[T; C]; R] {
+        return &self.matrix;
+    }
+
+    pub fn num_rows(&self) -> usize {
+        return R;
+    }
+
+    pub fn num_cols(&self) -> usize {
+        return C;
+    }
+
+    pub fn transpose(&self) -> SMatrix<C, R, T> {
+        let mut result = SMatrix::<C, R, T>::new();
+        for i in 0..R {
+            for j in 0..C {
+                result.matrix[j][i] = self.matrix[i][j];
+            }
+        }
+        return result;
+    }
+
+    pub fn to_matrix(self) -> GenericMatrix<T> {
+        return GenericMatrix::from_array(self.matrix);
+    }
+}
+
+impl<const N: usize, T: Scalar> SMatrix<N, N, T> {
+    pub fn identity() -> Self {
+        let mut result = Self::new();
+        for i in 0..N {
+            result.matrix[i][i] = T::one();
+        }
+        return result;
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> Default for SMatrix<R, C, T> {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> fmt::Display for SMatrix<R, C, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return fmt::Display::fmt(&self.to_matrix(), f);
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> From<SMatrix<R, C, T>> for GenericMatrix<T> {
+    fn from(m: SMatrix<R, C, T>) -> GenericMatrix<T> {
+        return m.to_matrix();
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> TryFrom<&GenericMatrix<T>> for SMatrix<R, C, T> {
+    type Error = MatrixError;
+
+    fn try_from(m: &GenericMatrix<T>) -> Result<Self, MatrixError> {
+        if m.rows != R || m.cols != C {
+            return Err(MatrixError::DimensionMismatch {
+                left: (m.rows, m.cols),
+                right: (R, C),
+            });
+        }
+
+        let mut result = Self::new();
+        for i in 0..R {
+            for j in 0..C {
+                result.matrix[i][j] = m.get(i, j);
+            }
+        }
+        return Ok(result);
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> TryFrom<GenericMatrix<T>> for SMatrix<R, C, T> {
+    type Error = MatrixError;
+
+    fn try_from(m: GenericMatrix<T>) -> Result<Self, MatrixError> {
+        return SMatrix::try_from(&m);
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> Index<(usize, usize)> for SMatrix<R, C, T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        return &self.matrix[row][col];
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> IndexMut<(usize, usize)> for SMatrix<R, C, T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        return &mut self.matrix[row][col];
+    }
+}
+
+impl<const R: usize, const C: usize, const K: usize, T: Scalar> Mul<SMatrix<C, K, T>>
+    for SMatrix<R, C, T>
+{
+    type Output = SMatrix<R, K, T>;
+
+    fn mul(self, rhs: SMatrix<C, K, T>) -> SMatrix<R, K, T> {
+        let mut result = SMatrix::<R, K, T>::new();
+        for i in 0..R {
+            for k in 0..C {
+                let a = self.matrix[i][k];
+                for j in 0..K {
+                    result.matrix[i][j] += a * rhs.matrix[k][j];
+                }
+            }
+        }
+        return result;
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> Add for SMatrix<R, C, T> {
+    type Output = SMatrix<R, C, T>;
+
+    fn add(mut self, rhs: SMatrix<R, C, T>) -> SMatrix<R, C, T> {
+        for i in 0..R {
+            for j in 0..C {
+                self.matrix[i][j] += rhs.matrix[i][j];
+            }
+        }
+        return self;
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> Sub for SMatrix<R, C, T> {
+    type Output = SMatrix<R, C, T>;
+
+    fn sub(self, rhs: SMatrix<R, C, T>) -> SMatrix<R, C, T> {
+        return self + -rhs;
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> Neg for SMatrix<R, C, T> {
+    type Output = SMatrix<R, C, T>;
+
+    fn neg(mut self) -> SMatrix<R, C, T> {
+        self.matrix.iter_mut().flatten().for_each(|x| *x = -*x);
+        return self;
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> Mul<T> for SMatrix<R, C, T> {
+    type Output = SMatrix<R, C, T>;
+
+    fn mul(mut self, rhs: T) -> SMatrix<R, C, T> {
+        self.matrix.iter_mut().flatten().for_each(|x| *x *= rhs);
+        return self;
+    }
+}
@@ -0,0 +1,225 @@
+use crate::matrix::fixed::SMatrix;
+use crate::matrix::scalar::Scalar;
+use crate::matrix::GenericMatrix;
+
+/// How many digits each element is printed with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Precision {
+    /// The element's own `Display` output, exact for `Rational`.
+    Full,
+    /// This many digits after the decimal point.
+    Fixed(usize),
+    /// This many significant digits, switching to scientific notation for
+    /// very large or very small magnitudes.
+    Significant(usize),
+}
+
+/// Placement of an element within its column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Alignment {
+    Left,
+    Right,
+    Center,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Layout {
+    /// One bracketed row per line, as printed by `Display`.
+    Text,
+    /// A LaTeX `bmatrix` environment.
+    Latex,
+    /// A Markdown table headed by column indices.
+    Markdown,
+    /// Nested brackets on a single line, without padding.
+    Compact,
+}
+
+/// Options for `GenericMatrix::format_with`.
+///
+/// ```text
+/// let format = MatrixFormat::new()
+///     .precision(Precision::Fixed(3))
+///     .layout(Layout::Latex);
+/// println!("{}", transform.format_with(&format));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatrixFormat {
+    precision: Precision,
+    alignment: Alignment,
+    layout: Layout,
+}
+
+impl MatrixFormat {
+    pub fn new() -> Self {
+        Self {
+            precision: Precision::Full,
+            alignment: Alignment::Right,
+            layout: Layout::Text,
+        }
+    }
+
+    pub fn precision(mut self, precision: Precision) -> Self {
+        self.precision = precision;
+        return self;
+    }
+
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        return self;
+    }
+
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        return self;
+    }
+}
+
+impl Default for MatrixFormat {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl<T: Scalar> GenericMatrix<T> {
+    pub fn format_with(&self, format: &MatrixFormat) -> String {
+        let cells: Vec<String> = self
+            .matrix
+            .iter()
+            .map(|x| format_element(*x, format.precision))
+            .collect();
+        let rows: Vec<&[String]> = match self.cols {
+            0 => vec![&[]; self.rows],
+            cols => cells.chunks(cols).collect(),
+        };
+
+        let widths: Vec<usize> = (0..self.cols)
+            .map(|j| rows.iter().map(|row| row[j].len()).max().unwrap_or(0))
+            .map(|width| match format.layout {
+                // A Markdown rule needs at least three characters.
+                Layout::Markdown => width.max(3),
+                _ => width,
+            })
+            .collect();
+        let padded = |row: &[String]| -> Vec<String> {
+            return row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| align(cell, *width, format.alignment))
+                .collect();
+        };
+
+        let mut result = String::new();
+        match format.layout {
+            Layout::Text => {
+                result.push('\n');
+                for row in &rows {
+                    let cells = padded(row);
+                    let separator = if cells.is_empty() { "" } else { " " };
+                    result.push_str(&format!("[ {}{}]\n", cells.join(" "), separator));
+                }
+                if rows.is_empty() {
+                    result.push_str("[ ]\n");
+                }
+            }
+            Layout::Latex => {
+                result.push_str("\\begin{bmatrix}\n");
+                let lines: Vec<String> = rows
+                    .iter()
+                    .map(|row| format!("    {}", padded(row).join(" & ")))
+                    .collect();
+                result.push_str(&lines.join(" \\\\\n"));
+                if !lines.is_empty() {
+                    result.push('\n');
+                }
+                result.push_str("\\end{bmatrix}");
+            }
+            Layout::Markdown => {
+                let header: Vec<String> = (0..self.cols)
+                    .map(|j| align(&j.to_string(), widths[j], format.alignment))
+                    .collect();
+                let rule: Vec<String> = widths
+                    .iter()
+                    .map(|width| markdown_rule(*width, format.alignment))
+                    .collect();
+
+                result.push_str(&markdown_row(&header));
+                result.push_str(&markdown_row(&rule));
+                for row in &rows {
+                    result.push_str(&markdown_row(&padded(row)));
+                }
+            }
+            Layout::Compact => {
+                let rows: Vec<String> = rows
+                    .iter()
+                    .map(|row| format!("[{}]", row.join(", ")))
+                    .collect();
+                result.push_str(&format!("[{}]", rows.join(", ")));
+            }
+        }
+
+        return result;
+    }
+}
+
+impl<const R: usize, const C: usize, T: Scalar> SMatrix<R, C, T> {
+    pub fn format_with(&self, format: &MatrixFormat) -> String {
+        return self.to_matrix().format_with(format);
+    }
+}
+
+fn format_element<T: Scalar>(x: T, precision: Precision) -> String {
+    let text = match precision {
+        Precision::Full => return x.to_string(),
+        Precision::Fixed(digits) => format!("{:.*}", digits, x.to_f64()),
+        Precision::Significant(digits) => significant(x.to_f64(), digits.max(1)),
+    };
+
+    // Rounding noise such as -0.0000001 would otherwise print as "-0.000".
+    if text.starts_with('-') && text.chars().all(|c| matches!(c, '-' | '0' | '.')) {
+        return text[1..].to_string();
+    }
+    return text;
+}
+
+/// Like C's `%g` but keeping trailing zeros, since they carry precision.
+fn significant(x: f64, digits: usize) -> String {
+    if !x.is_finite() {
+        return x.to_string();
+    }
+
+    let scientific = format!("{:.*e}", digits - 1, x);
+    let exponent: i64 = match scientific.split_once('e') {
+        Some((_, exponent)) => exponent.parse().unwrap_or(0),
+        None => 0,
+    };
+
+    if exponent < -4 || exponent >= digits as i64 {
+        return scientific;
+    }
+
+    // Reformat the already-rounded value so the digit count cannot change.
+    let rounded: f64 = scientific.parse().unwrap_or(x);
+    let decimals = (digits as i64 - 1 - exponent).max(0) as usize;
+    return format!("{:.*}", decimals, rounded);
+}
+
+fn align(cell: &str, width: usize, alignment: Alignment) -> String {
+    return match alignment {
+        Alignment::Left => format!("{:<width$}", cell),
+        Alignment::Right => format!("{:>width$}", cell),
+        Alignment::Center => format!("{:^width$}", cell),
+    };
+}
+
+fn markdown_rule(width: usize, alignment: Alignment) -> String {
+    let dashes = "-".repeat(width - 1);
+    return match alignment {
+        Alignment::Left => format!(":{}", dashes),
+        Alignment::Right => format!("{}:", dashes),
+        Alignment::Center => format!(":{}:", &dashes[1..]),
+    };
+}
+
+fn markdown_row(cells: &[String]) -> String {
+    return format!("| {} |\n", cells.join(" | "));
+}
@@ -0,0 +1,171 @@
+use crate::matrix::scalar::{Field, Real, Scalar};
+use crate::matrix::{GenericMatrix, MatrixError};
+
+/// Degree of the diagonal Padé approximant used by `exp`.
+const PADE_DEGREE: usize = 6;
+
+/// `exp` scales its input down to this 1-norm, where the degree 6 Padé
+/// approximant is accurate to double precision.
+const EXP_NORM: f64 = 0.5;
+
+/// `log` takes square roots until `|A - I|` is below this 1-norm.
+const LOG_NORM: f64 = 0.25;
+
+const MAX_ITERATIONS: usize = 64;
+
+impl<T: Scalar> GenericMatrix<T> {
+    /// `A^n` by repeated squaring, using about `2 * log2(n)` products.
+    pub fn pow(&self, n: u32) -> Result<Self, MatrixError> {
+        self.check_square()?;
+
+        let mut result = Self::identity(self.rows);
+        let mut base = self.clone();
+        let mut n = n;
+        while n > 0 {
+            if n & 1 == 1 {
+                result = &result * &base;
+            }
+            n >>= 1;
+            if n > 0 {
+                base = &base * &base;
+            }
+        }
+        return Ok(result);
+    }
+}
+
+impl<T: Field> GenericMatrix<T> {
+    /// `A^n` for any sign of `n`; negative powers go through the inverse.
+    pub fn powi(&self, n: i32) -> Result<Self, MatrixError> {
+        if n < 0 {
+            return self.inverse()?.pow(n.unsigned_abs());
+        }
+        return self.pow(n as u32);
+    }
+}
+
+impl<T: Real> GenericMatrix<T> {
+    /// Matrix exponential by scaling and squaring: `exp(A) = exp(A / 2^s)^(2^s)`
+    /// with `exp(A / 2^s)` from a diagonal Padé approximant. Fails with
+    /// `NonFinite` when `A` has NaN or infinite elements.
+    pub fn exp(&self) -> Result<Self, MatrixError> {
+        self.check_square()?;
+
+        if !all_finite(self) {
+            return Err(MatrixError::NonFinite);
+        }
+        let norm = norm_1(self).to_f64();
+        let squarings = (norm / EXP_NORM).log2().ceil().max(0.0) as i32;
+        let scaled = self * T::from_f64(0.5f64.powi(squarings));
+
+        // N(X) = sum c_k X^k and D(X) = N(-X), with exp(X) ~ D^-1 * N.
+        let identity = Self::identity(self.rows);
+        let mut numerator = identity.clone();
+        let mut denominator = identity.clone();
+        let mut power = identity;
+        let mut c = 1.0;
+        for k in 1..=PADE_DEGREE {
+            c *= (PADE_DEGREE - k + 1) as f64 / ((2 * PADE_DEGREE - k + 1) * k) as f64;
+            power = &power * &scaled;
+
+            let term = &power * T::from_f64(c);
+            numerator += &term;
+            if k % 2 == 0 {
+                denominator += &term;
+            } else {
+                denominator -= &term;
+            }
+        }
+
+        let mut result = denominator.solve(&numerator)?;
+        for _ in 0..squarings {
+            result = &result * &result;
+        }
+        return Ok(result);
+    }
+
+    /// Principal logarithm, the unique `X` with `exp(X) = A` whose eigenvalues
+    /// have imaginary parts in `(-pi, pi)`.
+    ///
+    /// Uses inverse scaling and squaring: square roots bring `A` close to the
+    /// identity, where the `atanh` series for `log` converges quickly. Fails
+    /// with `NoPrincipalLogarithm` when `A` is singular or has a negative real
+    /// eigenvalue, such as a rotation by exactly pi, and with `NonFinite`
+    /// when it has NaN or infinite elements.
+    pub fn log(&self) -> Result<Self, MatrixError> {
+        self.check_square()?;
+        if !all_finite(self) {
+            return Err(MatrixError::NonFinite);
+        }
+
+        let n = self.rows;
+        let identity = Self::identity(n);
+        let mut root = self.clone();
+        let mut square_roots = 0;
+        while norm_1(&(&root - &identity)).to_f64() > LOG_NORM {
+            if square_roots == MAX_ITERATIONS {
+                return Err(MatrixError::NoPrincipalLogarithm);
+            }
+            root = root.sqrt_denman_beavers()?;
+            square_roots += 1;
+        }
+
+        // log(A) = 2 * atanh(Z) = 2 * (Z + Z^3 / 3 + Z^5 / 5 + ...),
+        // with Z = (A - I)(A + I)^-1.
+        // The two factors commute, so Z = (A + I)^-1 (A - I) as well.
+        let z = (&root + &identity)
+            .solve(&(&root - &identity))
+            .map_err(|_| MatrixError::NoPrincipalLogarithm)?;
+        let z_squared = &z * &z;
+
+        let mut sum = z.clone();
+        let mut power = z;
+        for k in 1..MAX_ITERATIONS {
+            power = &power * &z_squared;
+            let term = &power * T::from_f64(1.0 / (2 * k + 1) as f64);
+            sum += &term;
+            if norm_1(&term) <= T::epsilon() * norm_1(&sum) {
+                break;
+            }
+        }
+
+        return Ok(sum * T::from_f64(2.0 * 2f64.powi(square_roots as i32)));
+    }
+
+    /// Principal square root by the Denman-Beavers iteration.
+    fn sqrt_denman_beavers(&self) -> Result<Self, MatrixError> {
+        let half = T::from_f64(0.5);
+        let mut y = self.clone();
+        let mut z = Self::identity(self.rows);
+
+        // Convergence is quadratic, so one more step after the change drops
+        // below sqrt(epsilon) reaches rounding level.
+        let mut converging = false;
+        for _ in 0..MAX_ITERATIONS {
+            let y_inverse = y.inverse().map_err(|_| MatrixError::NoPrincipalLogarithm)?;
+            let z_inverse = z.inverse().map_err(|_| MatrixError::NoPrincipalLogarithm)?;
+            let next = (&y + &z_inverse) * half;
+            z = (&z + &y_inverse) * half;
+
+            let change = norm_1(&(&next - &y));
+            y = next;
+            if converging {
+                return Ok(y);
+            }
+            converging = change <= T::epsilon().sqrt() * norm_1(&y);
+        }
+        return Err(MatrixError::NoPrincipalLogarithm);
+    }
+}
+
+fn all_finite<T: Real>(m: &GenericMatrix<T>) -> bool {
+    return m.matrix.iter().all(|x| x.to_f64().is_finite());
+}
+
+/// Largest absolute column sum.
+fn norm_1<T: Real>(m: &GenericMatrix<T>) -> T {
+    return m
+        .columns()
+        .map(|col| col.map(|x| x.abs()).sum::<T>())
+        .fold(T::zero(), |max, x| if x > max { x } else { max });
+}
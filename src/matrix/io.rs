@@ -0,0 +1,522 @@
+//! Readers and writers for exchanging matrices with other tools.
+//!
+//! - CSV: one row per line, elements separated by commas.
+//! - Matrix Market (`.mtx`): the `array` (dense, column-major) and
+//!   `coordinate` (sparse, 1-based `row col value`) formats, with `real`,
+//!   `integer` and `pattern` fields and `general`, `symmetric` and
+//!   `skew-symmetric` storage.
+//! - Binary: the magic bytes `RGMX`, the element width in bytes (4 or 8),
+//!   rows and columns as little-endian `u64`, then the elements row by row
+//!   as little-endian IEEE floats.
+//!
+//! Text formats report parse errors with 1-based line and column numbers.
+
+use core::fmt;
+use std::io::{self, BufRead, Read, Write};
+use std::mem::size_of;
+use std::str::FromStr;
+
+use crate::matrix::scalar::Real;
+use crate::matrix::sparse::{CooMatrix, CsrMatrix};
+use crate::matrix::GenericMatrix;
+
+const BINARY_MAGIC: &[u8; 4] = b"RGMX";
+
+/// Largest element count `GenericMatrix::read_matrix_market` allocates. A
+/// coordinate file can declare any size in a few bytes, so bigger matrices
+/// have to go through `CsrMatrix::read_matrix_market`.
+pub const MAX_DENSE_ELEMENTS: usize = 1 << 26;
+
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    Corrupt {
+        offset: usize,
+        message: String,
+    },
+}
+
+/// Layout written by `write_matrix_market`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MarketFormat {
+    /// Every element, column by column.
+    Array,
+    /// Only the non-zero elements, with their positions.
+    Coordinate,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Symmetry {
+    General,
+    Symmetric,
+    SkewSymmetric,
+}
+
+/// Shape and entries read from a Matrix Market file.
+type Entries<T> = (usize, usize, Vec<(usize, usize, T)>);
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadError::Io(e) => return write!(f, "{}", e),
+            ReadError::Parse {
+                line,
+                column,
+                message,
+            } => return write!(f, "line {}, column {}: {}", line, column, message),
+            ReadError::Corrupt { offset, message } => {
+                return write!(f, "byte {}: {}", offset, message);
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadError::Io(e) => return Some(e),
+            _ => return None,
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> ReadError {
+        return ReadError::Io(e);
+    }
+}
+
+fn parse_error(line: usize, column: usize, message: impl Into<String>) -> ReadError {
+    return ReadError::Parse {
+        line,
+        column,
+        message: message.into(),
+    };
+}
+
+fn parse_value<T: FromStr>(text: &str, line: usize, column: usize) -> Result<T, ReadError> {
+    return text
+        .parse()
+        .map_err(|_| parse_error(line, column, format!("expected a number, found `{}`", text)));
+}
+
+/// Whitespace-separated tokens of `line` with their 1-based columns.
+fn tokens(line: &str) -> Vec<(usize, &str)> {
+    let mut result = Vec::new();
+    let mut start = None;
+
+    for (i, c) in line.char_indices().chain([(line.len(), ' ')]) {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some(i),
+            (Some(s), true) => {
+                result.push((s + 1, &line[s..i]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    return result;
+}
+
+impl<T: Real + FromStr> GenericMatrix<T> {
+    pub fn read_csv<R: BufRead>(reader: R) -> Result<Self, ReadError> {
+        let mut matrix = Vec::new();
+        let (mut rows, mut cols) = (0, None);
+        let mut last_line = 0;
+
+        for (index, line) in reader.lines().enumerate() {
+            let (line, number) = (line?, index + 1);
+            last_line = number;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let mut offset = 0;
+            let mut columns = Vec::new();
+            for field in line.split(',') {
+                let trimmed = field.trim_start();
+                let column = offset + field.len() - trimmed.len() + 1;
+                matrix.push(parse_value(trimmed.trim_end(), number, column)?);
+                columns.push(column);
+                offset += field.len() + 1;
+            }
+
+            let expected = *cols.get_or_insert(columns.len());
+            if columns.len() != expected {
+                let column = columns.get(expected).copied().unwrap_or(line.len() + 1);
+                let message = format!("row has {} fields, expected {}", columns.len(), expected);
+                return Err(parse_error(number, column, message));
+            }
+            rows += 1;
+        }
+
+        return match cols {
+            Some(cols) => Ok(Self { rows, cols, matrix }),
+            None => Err(parse_error(last_line + 1, 1, "no matrix data")),
+        };
+    }
+
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for row in self.matrix.chunks(self.cols.max(1)) {
+            let fields: Vec<String> = row.iter().map(|x| x.to_string()).collect();
+            writeln!(writer, "{}", fields.join(","))?;
+        }
+        return Ok(());
+    }
+
+    /// Reads either Matrix Market format. Entries repeated in a coordinate
+    /// file are summed. Sizes over `MAX_DENSE_ELEMENTS` are rejected.
+    pub fn read_matrix_market<R: BufRead>(reader: R) -> Result<Self, ReadError> {
+        let (rows, cols, entries) = read_market(reader, true)?;
+
+        let mut result = Self::new(rows, cols);
+        for (i, j, value) in entries {
+            let sum = result.get(i, j) + value;
+            result.put(i, j, sum);
+        }
+        return Ok(result);
+    }
+
+    pub fn write_matrix_market<W: Write>(
+        &self,
+        mut writer: W,
+        format: MarketFormat,
+    ) -> io::Result<()> {
+        match format {
+            MarketFormat::Array => {
+                writeln!(writer, "%%MatrixMarket matrix array real general")?;
+                writeln!(writer, "{} {}", self.rows, self.cols)?;
+                for j in 0..self.cols {
+                    for i in 0..self.rows {
+                        writeln!(writer, "{}", self.get(i, j))?;
+                    }
+                }
+            }
+            MarketFormat::Coordinate => {
+                CsrMatrix::from_dense(self).write_matrix_market(writer)?;
+            }
+        }
+        return Ok(());
+    }
+
+    pub fn read_binary<R: Read>(mut reader: R) -> Result<Self, ReadError> {
+        let mut header = [0u8; 21];
+        read_exact_at(&mut reader, &mut header, 0)?;
+
+        if &header[0..4] != BINARY_MAGIC {
+            return Err(corrupt(0, "missing RGMX magic bytes"));
+        }
+        let width = header[4] as usize;
+        if width != 4 && width != 8 {
+            return Err(corrupt(4, format!("unsupported element width {}", width)));
+        }
+
+        let rows = u64::from_le_bytes(header[5..13].try_into().expect("8 bytes"));
+        let cols = u64::from_le_bytes(header[13..21].try_into().expect("8 bytes"));
+        let length = usize::try_from(rows)
+            .ok()
+            .zip(usize::try_from(cols).ok())
+            .and_then(|(r, c)| r.checked_mul(c)?.checked_mul(width));
+        let length = match length {
+            Some(length) => length,
+            None => return Err(corrupt(5, format!("{}x{} is too large", rows, cols))),
+        };
+
+        // The header is not trusted to size the buffer: it only grows as
+        // bytes actually arrive.
+        let mut data = Vec::new();
+        reader.take(length as u64).read_to_end(&mut data)?;
+        if data.len() < length {
+            let message = format!("expected {} bytes of data, found {}", length, data.len());
+            return Err(corrupt(header.len(), message));
+        }
+
+        let matrix = data
+            .chunks_exact(width)
+            .map(|bytes| match width {
+                4 => T::from_f64(f32::from_le_bytes(bytes.try_into().expect("4 bytes")) as f64),
+                _ => T::from_f64(f64::from_le_bytes(bytes.try_into().expect("8 bytes"))),
+            })
+            .collect();
+
+        return Ok(Self {
+            rows: rows as usize,
+            cols: cols as usize,
+            matrix,
+        });
+    }
+
+    /// Writes elements at the width of `T`, so `f32` and `f64` both round-trip
+    /// exactly.
+    pub fn write_binary<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let width = size_of::<T>().min(8);
+
+        writer.write_all(BINARY_MAGIC)?;
+        writer.write_all(&[width as u8])?;
+        writer.write_all(&(self.rows as u64).to_le_bytes())?;
+        writer.write_all(&(self.cols as u64).to_le_bytes())?;
+        for x in &self.matrix {
+            match width {
+                4 => writer.write_all(&(x.to_f64() as f32).to_le_bytes())?,
+                _ => writer.write_all(&x.to_f64().to_le_bytes())?,
+            }
+        }
+        return Ok(());
+    }
+}
+
+impl<T: Real + FromStr> CsrMatrix<T> {
+    /// Reads either Matrix Market format without building a dense matrix.
+    pub fn read_matrix_market<R: BufRead>(reader: R) -> Result<Self, ReadError> {
+        let (rows, cols, entries) = read_market(reader, false)?;
+
+        let mut coo = CooMatrix::new(rows, cols);
+        for (i, j, value) in entries {
+            coo.push(i, j, value).expect("read_market checks bounds");
+        }
+        return Ok(coo.to_csr());
+    }
+
+    /// Writes the stored entries in coordinate format.
+    pub fn write_matrix_market<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "%%MatrixMarket matrix coordinate real general")?;
+        writeln!(
+            writer,
+            "{} {} {}",
+            self.num_rows(),
+            self.num_cols(),
+            self.nnz()
+        )?;
+        for i in 0..self.num_rows() {
+            for (j, value) in self.row(i).expect("i < num_rows") {
+                writeln!(writer, "{} {} {}", i + 1, j + 1, value)?;
+            }
+        }
+        return Ok(());
+    }
+}
+
+fn corrupt(offset: usize, message: impl Into<String>) -> ReadError {
+    return ReadError::Corrupt {
+        offset,
+        message: message.into(),
+    };
+}
+
+fn read_exact_at<R: Read>(reader: &mut R, buf: &mut [u8], offset: usize) -> Result<(), ReadError> {
+    return reader.read_exact(buf).map_err(|e| match e.kind() {
+        io::ErrorKind::UnexpectedEof => {
+            corrupt(offset, format!("expected {} more bytes", buf.len()))
+        }
+        _ => ReadError::Io(e),
+    });
+}
+
+/// Reads the header, size line and entries. With `dense` set, the size is
+/// also checked against `MAX_DENSE_ELEMENTS`.
+fn read_market<T: Real + FromStr, R: BufRead>(
+    reader: R,
+    dense: bool,
+) -> Result<Entries<T>, ReadError> {
+    let mut lines = reader.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+    let (number, banner) = match lines.next() {
+        Some((number, line)) => (number, line?),
+        None => return Err(parse_error(1, 1, "missing %%MatrixMarket header")),
+    };
+    let header = tokens(&banner);
+    let word = |k: usize| header.get(k).map(|(_, t)| t.to_ascii_lowercase());
+    let column = |k: usize| header.get(k).map_or(banner.len() + 1, |(c, _)| *c);
+
+    if word(0).as_deref() != Some("%%matrixmarket") {
+        return Err(parse_error(number, 1, "missing %%MatrixMarket header"));
+    }
+    if word(1).as_deref() != Some("matrix") {
+        return Err(parse_error(number, column(1), "expected object `matrix`"));
+    }
+    let coordinate = match word(2).as_deref() {
+        Some("array") => false,
+        Some("coordinate") => true,
+        _ => {
+            let message = "expected format `array` or `coordinate`";
+            return Err(parse_error(number, column(2), message));
+        }
+    };
+    let pattern = match word(3).as_deref() {
+        Some("real") | Some("double") | Some("integer") => false,
+        Some("pattern") if coordinate => true,
+        _ => {
+            let message = "unsupported field, expected `real`, `integer` or `pattern`";
+            return Err(parse_error(number, column(3), message));
+        }
+    };
+    let symmetry = match word(4).as_deref() {
+        Some("general") => Symmetry::General,
+        Some("symmetric") => Symmetry::Symmetric,
+        Some("skew-symmetric") => Symmetry::SkewSymmetric,
+        _ => {
+            let message =
+                "unsupported symmetry, expected `general`, `symmetric` or `skew-symmetric`";
+            return Err(parse_error(number, column(4), message));
+        }
+    };
+
+    let mut last_line = number;
+    let mut data = Vec::new();
+    for (number, line) in lines {
+        let line = line?;
+        last_line = number;
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('%') {
+            continue;
+        }
+        data.push((number, line));
+    }
+    let mut data = data.into_iter();
+
+    let size_fields = if coordinate { 3 } else { 2 };
+    let (number, size_line) = match data.next() {
+        Some(line) => line,
+        None => return Err(parse_error(last_line + 1, 1, "missing size line")),
+    };
+    let size = parse_fields::<usize>(&size_line, number, size_fields)?;
+    let (rows, cols) = (size[0], size[1]);
+    if symmetry != Symmetry::General && rows != cols {
+        let message = format!(
+            "symmetric storage needs a square matrix, found {}x{}",
+            rows, cols
+        );
+        return Err(parse_error(number, 1, message));
+    }
+    let elements = rows.checked_mul(cols);
+    if dense && elements.is_none_or(|n| n > MAX_DENSE_ELEMENTS) {
+        let message = format!(
+            "{}x{} is too large for a dense matrix, use CsrMatrix::read_matrix_market",
+            rows, cols
+        );
+        return Err(parse_error(number, 1, message));
+    }
+
+    let mut entries = Vec::new();
+    let mut push = |i: usize, j: usize, value: T| {
+        entries.push((i, j, value));
+        match symmetry {
+            Symmetry::Symmetric if i != j => entries.push((j, i, value)),
+            Symmetry::SkewSymmetric => entries.push((j, i, -value)),
+            _ => {}
+        }
+    };
+
+    let expected = if coordinate {
+        Some(size[2])
+    } else {
+        match symmetry {
+            Symmetry::General => elements,
+            Symmetry::Symmetric => rows.checked_mul(rows.saturating_add(1)).map(|n| n / 2),
+            Symmetry::SkewSymmetric => rows.checked_mul(rows.saturating_sub(1)).map(|n| n / 2),
+        }
+    };
+    let expected = match expected {
+        Some(expected) => expected,
+        None => {
+            let message = format!("{}x{} is too large", rows, cols);
+            return Err(parse_error(number, 1, message));
+        }
+    };
+
+    // Dense storage is column-major, and symmetric storage keeps only the
+    // lower triangle (strictly lower when skew-symmetric).
+    let first_row = |j: usize| match symmetry {
+        Symmetry::General => 0,
+        Symmetry::Symmetric => j,
+        Symmetry::SkewSymmetric => j + 1,
+    };
+    let mut position = (0, first_row(0));
+
+    for count in 0..=expected {
+        let (number, line) = match data.next() {
+            Some(line) => line,
+            None if count == expected => break,
+            None => {
+                let message = format!("expected {} entries, found {}", expected, count);
+                return Err(parse_error(last_line + 1, 1, message));
+            }
+        };
+        if count == expected {
+            let message = format!("expected {} entries, found more", expected);
+            return Err(parse_error(number, 1, message));
+        }
+
+        if coordinate {
+            let fields = tokens(&line);
+            let wanted = if pattern { 2 } else { 3 };
+            check_field_count(&line, &fields, number, wanted)?;
+
+            let index = |k: usize, limit: usize| {
+                let (column, text) = fields[k];
+                let value: usize = parse_value(text, number, column)?;
+                if value == 0 || value > limit {
+                    let message = format!("index {} out of range 1..={}", value, limit);
+                    return Err(parse_error(number, column, message));
+                }
+                return Ok(value - 1);
+            };
+            let (i, j) = (index(0, rows)?, index(1, cols)?);
+
+            let value = if pattern {
+                T::one()
+            } else {
+                parse_value(fields[2].1, number, fields[2].0)?
+            };
+            if symmetry != Symmetry::General && j > i {
+                let message = "symmetric storage only holds the lower triangle";
+                return Err(parse_error(number, fields[1].0, message));
+            }
+            if symmetry == Symmetry::SkewSymmetric && j == i {
+                let message = "skew-symmetric storage only holds the strictly lower triangle";
+                return Err(parse_error(number, fields[1].0, message));
+            }
+            push(i, j, value);
+        } else {
+            let value = parse_fields::<T>(&line, number, 1)?[0];
+            let (j, i) = position;
+            push(i, j, value);
+
+            position = if i + 1 < rows {
+                (j, i + 1)
+            } else {
+                (j + 1, first_row(j + 1))
+            };
+        }
+    }
+
+    return Ok((rows, cols, entries));
+}
+
+fn check_field_count(
+    line: &str,
+    fields: &[(usize, &str)],
+    number: usize,
+    wanted: usize,
+) -> Result<(), ReadError> {
+    if fields.len() != wanted {
+        let column = fields.get(wanted).map_or(line.len() + 1, |(c, _)| *c);
+        let message = format!("expected {} fields, found {}", wanted, fields.len());
+        return Err(parse_error(number, column, message));
+    }
+    return Ok(());
+}
+
+fn parse_fields<T: FromStr>(line: &str, number: usize, wanted: usize) -> Result<Vec<T>, ReadError> {
+    let fields = tokens(line);
+    check_field_count(line, &fields, number, wanted)?;
+    return fields
+        .iter()
+        .map(|(column, text)| parse_value(text, number, *column))
+        .collect();
+}
@@ -0,0 +1,233 @@
+//! Operator overloads for `Matrix`.
+//!
+//! Operators mirror the checked methods but panic when shapes do not match,
+//! the same way slice indexing panics when out of range. The panic message is
+//! the `MatrixError` the checked method would have returned. Use
+//! `matrix_add`, `matrix_subtract` and `matrix_multiply_blocked` where a
+//! mismatch is an expected, recoverable condition.
+
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
+};
+
+use crate::matrix::scalar::{Field, Rational, Scalar};
+use crate::matrix::{GenericMatrix, MatrixError};
+
+fn expect_shape<T>(result: Result<GenericMatrix<T>, MatrixError>) -> GenericMatrix<T> {
+    match result {
+        Ok(m) => return m,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+impl<T: Scalar> Index<(usize, usize)> for GenericMatrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        if let Err(e) = self.check_bounds(row, col) {
+            panic!("{}", e);
+        }
+        return &self.matrix[self.cols * row + col];
+    }
+}
+
+impl<T: Scalar> IndexMut<(usize, usize)> for GenericMatrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        if let Err(e) = self.check_bounds(row, col) {
+            panic!("{}", e);
+        }
+        return &mut self.matrix[self.cols * row + col];
+    }
+}
+
+impl<T: Scalar> Add<&GenericMatrix<T>> for &GenericMatrix<T> {
+    type Output = GenericMatrix<T>;
+
+    fn add(self, rhs: &GenericMatrix<T>) -> GenericMatrix<T> {
+        return expect_shape(self.matrix_add(rhs));
+    }
+}
+
+impl<T: Scalar> Sub<&GenericMatrix<T>> for &GenericMatrix<T> {
+    type Output = GenericMatrix<T>;
+
+    fn sub(self, rhs: &GenericMatrix<T>) -> GenericMatrix<T> {
+        return expect_shape(self.matrix_subtract(rhs));
+    }
+}
+
+impl<T: Scalar> Mul<&GenericMatrix<T>> for &GenericMatrix<T> {
+    type Output = GenericMatrix<T>;
+
+    fn mul(self, rhs: &GenericMatrix<T>) -> GenericMatrix<T> {
+        return expect_shape(self.matrix_multiply_blocked(rhs));
+    }
+}
+
+/// Forwards the owned and mixed forms of a binary operator to the
+/// `&Matrix op &Matrix` implementation.
+macro_rules! forward_binop {
+    ($imp:ident, $method:ident) => {
+        impl<T: Scalar> $imp<GenericMatrix<T>> for GenericMatrix<T> {
+            type Output = GenericMatrix<T>;
+
+            fn $method(self, rhs: GenericMatrix<T>) -> GenericMatrix<T> {
+                return (&self).$method(&rhs);
+            }
+        }
+
+        impl<T: Scalar> $imp<&GenericMatrix<T>> for GenericMatrix<T> {
+            type Output = GenericMatrix<T>;
+
+            fn $method(self, rhs: &GenericMatrix<T>) -> GenericMatrix<T> {
+                return (&self).$method(rhs);
+            }
+        }
+
+        impl<T: Scalar> $imp<GenericMatrix<T>> for &GenericMatrix<T> {
+            type Output = GenericMatrix<T>;
+
+            fn $method(self, rhs: GenericMatrix<T>) -> GenericMatrix<T> {
+                return self.$method(&rhs);
+            }
+        }
+    };
+}
+
+forward_binop!(Add, add);
+forward_binop!(Sub, sub);
+forward_binop!(Mul, mul);
+
+/// Forwards `Matrix op= Matrix` to the `Matrix op= &Matrix` implementation.
+macro_rules! forward_assign {
+    ($imp:ident, $method:ident) => {
+        impl<T: Scalar> $imp<GenericMatrix<T>> for GenericMatrix<T> {
+            fn $method(&mut self, rhs: GenericMatrix<T>) {
+                self.$method(&rhs);
+            }
+        }
+    };
+}
+
+impl<T: Scalar> AddAssign<&GenericMatrix<T>> for GenericMatrix<T> {
+    fn add_assign(&mut self, rhs: &GenericMatrix<T>) {
+        if let Err(e) = self.check_same_shape(rhs) {
+            panic!("{}", e);
+        }
+        self.matrix
+            .iter_mut()
+            .zip(rhs.matrix.iter())
+            .for_each(|(x, y)| *x += *y);
+    }
+}
+
+impl<T: Scalar> SubAssign<&GenericMatrix<T>> for GenericMatrix<T> {
+    fn sub_assign(&mut self, rhs: &GenericMatrix<T>) {
+        if let Err(e) = self.check_same_shape(rhs) {
+            panic!("{}", e);
+        }
+        self.matrix
+            .iter_mut()
+            .zip(rhs.matrix.iter())
+            .for_each(|(x, y)| *x -= *y);
+    }
+}
+
+impl<T: Scalar> MulAssign<&GenericMatrix<T>> for GenericMatrix<T> {
+    fn mul_assign(&mut self, rhs: &GenericMatrix<T>) {
+        *self = &*self * rhs;
+    }
+}
+
+forward_assign!(AddAssign, add_assign);
+forward_assign!(SubAssign, sub_assign);
+forward_assign!(MulAssign, mul_assign);
+
+impl<T: Scalar> Neg for GenericMatrix<T> {
+    type Output = GenericMatrix<T>;
+
+    fn neg(mut self) -> GenericMatrix<T> {
+        self.matrix.iter_mut().for_each(|x| *x = -*x);
+        return self;
+    }
+}
+
+impl<T: Scalar> Neg for &GenericMatrix<T> {
+    type Output = GenericMatrix<T>;
+
+    fn neg(self) -> GenericMatrix<T> {
+        return -self.clone();
+    }
+}
+
+impl<T: Scalar> Mul<T> for GenericMatrix<T> {
+    type Output = GenericMatrix<T>;
+
+    fn mul(mut self, rhs: T) -> GenericMatrix<T> {
+        self.multiply(rhs);
+        return self;
+    }
+}
+
+impl<T: Scalar> Mul<T> for &GenericMatrix<T> {
+    type Output = GenericMatrix<T>;
+
+    fn mul(self, rhs: T) -> GenericMatrix<T> {
+        return self.clone() * rhs;
+    }
+}
+
+impl<T: Field> Div<T> for GenericMatrix<T> {
+    type Output = GenericMatrix<T>;
+
+    fn div(mut self, rhs: T) -> GenericMatrix<T> {
+        self.matrix.iter_mut().for_each(|x| *x /= rhs);
+        return self;
+    }
+}
+
+impl<T: Field> Div<T> for &GenericMatrix<T> {
+    type Output = GenericMatrix<T>;
+
+    fn div(self, rhs: T) -> GenericMatrix<T> {
+        return self.clone() / rhs;
+    }
+}
+
+impl<T: Scalar> MulAssign<T> for GenericMatrix<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.multiply(rhs);
+    }
+}
+
+impl<T: Field> DivAssign<T> for GenericMatrix<T> {
+    fn div_assign(&mut self, rhs: T) {
+        self.matrix.iter_mut().for_each(|x| *x /= rhs);
+    }
+}
+
+/// `scalar * matrix` has the scalar type on the left, so it cannot be written
+/// generically and is implemented once per element type instead.
+macro_rules! scalar_lhs_mul {
+    ($($t:ty),*) => {
+        $(
+            impl Mul<GenericMatrix<$t>> for $t {
+                type Output = GenericMatrix<$t>;
+
+                fn mul(self, rhs: GenericMatrix<$t>) -> GenericMatrix<$t> {
+                    return rhs * self;
+                }
+            }
+
+            impl Mul<&GenericMatrix<$t>> for $t {
+                type Output = GenericMatrix<$t>;
+
+                fn mul(self, rhs: &GenericMatrix<$t>) -> GenericMatrix<$t> {
+                    return rhs * self;
+                }
+            }
+        )*
+    };
+}
+
+scalar_lhs_mul!(f32, f64, i32, i64, Rational);
@@ -0,0 +1,316 @@
+use core::fmt;
+use std::cmp::Ordering;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Element type of a matrix: anything with ring arithmetic.
+pub trait Scalar:
+    Copy
+    + PartialEq
+    + PartialOrd
+    + fmt::Debug
+    + fmt::Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + Sum
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn from_usize(n: usize) -> Self;
+    fn abs(self) -> Self;
+    /// Nearest `f64`, for display and diagnostics.
+    fn to_f64(self) -> f64;
+}
+
+/// Scalars with division, needed for elimination (inverse, LU, rank).
+pub trait Field: Scalar + Div<Output = Self> + DivAssign {
+    /// Relative rounding error of one operation. Zero for exact types, so
+    /// only true zeros count as zero pivots.
+    fn epsilon() -> Self;
+}
+
+/// Floating point scalars, needed where an algorithm takes square roots.
+pub trait Real: Field {
+    fn sqrt(self) -> Self;
+    fn signum(self) -> Self;
+    fn from_f64(v: f64) -> Self;
+    /// Number of representable values between `self` and `other`. Zeros of
+    /// either sign are one value; a NaN is infinitely far from everything.
+    fn ulps_between(self, other: Self) -> u64;
+}
+
+macro_rules! impl_real {
+    ($t:ident, $bits:ident) => {
+        impl Scalar for $t {
+            fn zero() -> Self {
+                return 0.0;
+            }
+
+            fn one() -> Self {
+                return 1.0;
+            }
+
+            fn from_usize(n: usize) -> Self {
+                return n as $t;
+            }
+
+            fn abs(self) -> Self {
+                return $t::abs(self);
+            }
+
+            fn to_f64(self) -> f64 {
+                return self as f64;
+            }
+        }
+
+        impl Field for $t {
+            fn epsilon() -> Self {
+                return $t::EPSILON;
+            }
+        }
+
+        impl Real for $t {
+            fn sqrt(self) -> Self {
+                return $t::sqrt(self);
+            }
+
+            fn signum(self) -> Self {
+                return $t::signum(self);
+            }
+
+            fn from_f64(v: f64) -> Self {
+                return v as $t;
+            }
+
+            fn ulps_between(self, other: Self) -> u64 {
+                if self.is_nan() || other.is_nan() {
+                    return u64::MAX;
+                }
+
+                // Reinterpret sign-magnitude bits as a two's complement
+                // integer so that adjacent floats are adjacent integers.
+                let ordered = |x: $t| {
+                    let bits = x.to_bits() as $bits;
+                    return if bits < 0 { $bits::MIN - bits } else { bits };
+                };
+                return (ordered(self) as i128 - ordered(other) as i128).unsigned_abs() as u64;
+            }
+        }
+    };
+}
+
+macro_rules! impl_integer {
+    ($t:ident) => {
+        impl Scalar for $t {
+            fn zero() -> Self {
+                return 0;
+            }
+
+            fn one() -> Self {
+                return 1;
+            }
+
+            fn from_usize(n: usize) -> Self {
+                return n as $t;
+            }
+
+            fn abs(self) -> Self {
+                return $t::abs(self);
+            }
+
+            fn to_f64(self) -> f64 {
+                return self as f64;
+            }
+        }
+    };
+}
+
+impl_real!(f32, i32);
+impl_real!(f64, i64);
+impl_integer!(i32);
+impl_integer!(i64);
+
+/// Exact fraction `num / den`, always stored in lowest terms with `den > 0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(dead_code)]
+pub struct Rational {
+    num: i64,
+    den: i64,
+}
+
+#[allow(dead_code)]
+impl Rational {
+    pub fn new(num: i64, den: i64) -> Self {
+        return Rational::reduce(num as i128, den as i128);
+    }
+
+    pub fn from_integer(num: i64) -> Self {
+        return Rational { num, den: 1 };
+    }
+
+    pub fn numerator(&self) -> i64 {
+        return self.num;
+    }
+
+    pub fn denominator(&self) -> i64 {
+        return self.den;
+    }
+
+    /// Intermediate results are widened so that products of two in-range
+    /// fractions do not overflow before they are reduced. Panics if the
+    /// reduced fraction still does not fit in `i64`.
+    fn reduce(num: i128, den: i128) -> Self {
+        if den == 0 {
+            panic!("Rational with zero denominator");
+        }
+
+        let divisor = gcd(num, den) * den.signum();
+        let (num, den) = (num / divisor, den / divisor);
+        return match (i64::try_from(num), i64::try_from(den)) {
+            (Ok(num), Ok(den)) => Rational { num, den },
+            _ => panic!("Rational overflow: {}/{} does not fit in i64", num, den),
+        };
+    }
+}
+
+#[allow(dead_code)]
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    return a.max(1);
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.den == 1 {
+            return write!(f, "{}", self.num);
+        }
+        return write!(f, "{}/{}", self.num, self.den);
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        let lhs = self.num as i128 * other.den as i128;
+        let rhs = other.num as i128 * self.den as i128;
+        return lhs.cmp(&rhs);
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Rational) -> Rational {
+        let num = self.num as i128 * rhs.den as i128 + rhs.num as i128 * self.den as i128;
+        return Rational::reduce(num, self.den as i128 * rhs.den as i128);
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Rational) -> Rational {
+        return self + -rhs;
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Rational) -> Rational {
+        return Rational::reduce(
+            self.num as i128 * rhs.num as i128,
+            self.den as i128 * rhs.den as i128,
+        );
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, rhs: Rational) -> Rational {
+        return Rational::reduce(
+            self.num as i128 * rhs.den as i128,
+            self.den as i128 * rhs.num as i128,
+        );
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        return Rational::reduce(-(self.num as i128), self.den as i128);
+    }
+}
+
+impl AddAssign for Rational {
+    fn add_assign(&mut self, rhs: Rational) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Rational {
+    fn sub_assign(&mut self, rhs: Rational) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Rational {
+    fn mul_assign(&mut self, rhs: Rational) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for Rational {
+    fn div_assign(&mut self, rhs: Rational) {
+        *self = *self / rhs;
+    }
+}
+
+impl Sum for Rational {
+    fn sum<I: Iterator<Item = Rational>>(iter: I) -> Rational {
+        return iter.fold(Rational::zero(), |acc, x| acc + x);
+    }
+}
+
+impl Scalar for Rational {
+    fn zero() -> Self {
+        return Rational::from_integer(0);
+    }
+
+    fn one() -> Self {
+        return Rational::from_integer(1);
+    }
+
+    fn from_usize(n: usize) -> Self {
+        return Rational::from_integer(n as i64);
+    }
+
+    fn abs(self) -> Self {
+        return Rational::reduce((self.num as i128).abs(), self.den as i128);
+    }
+
+    fn to_f64(self) -> f64 {
+        return self.num as f64 / self.den as f64;
+    }
+}
+
+impl Field for Rational {
+    fn epsilon() -> Self {
+        return Rational::zero();
+    }
+}
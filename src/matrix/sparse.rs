@@ -0,0 +1,364 @@
+use std::ops::Mul;
+
+use crate::matrix::scalar::{Real, Scalar};
+use crate::matrix::{GenericMatrix, MatrixError};
+
+/// Coordinate-format builder: an unordered list of `(row, col, value)`
+/// entries. Duplicate entries are summed when converted to CSR, which is how
+/// per-element contributions to a mesh Laplacian are usually assembled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CooMatrix<T> {
+    rows: usize,
+    cols: usize,
+    entries: Vec<(usize, usize, T)>,
+}
+
+/// Compressed sparse row storage.
+///
+/// The entries of row `i` are `values[row_ptr[i]..row_ptr[i + 1]]`, at the
+/// columns in the same range of `col_idx`, sorted by column with no
+/// duplicates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrMatrix<T> {
+    rows: usize,
+    cols: usize,
+    row_ptr: Vec<usize>,
+    col_idx: Vec<usize>,
+    values: Vec<T>,
+}
+
+/// Result of `CsrMatrix::solve_cg`.
+#[derive(Debug, Clone)]
+pub struct CgSolution<T> {
+    solution: Vec<T>,
+    iterations: usize,
+    residual: T,
+}
+
+impl<T: Scalar> CgSolution<T> {
+    pub fn solution(&self) -> &[T] {
+        return &self.solution;
+    }
+
+    pub fn iterations(&self) -> usize {
+        return self.iterations;
+    }
+
+    /// Norm of `b - A * x` at the returned solution.
+    pub fn residual(&self) -> T {
+        return self.residual;
+    }
+}
+
+impl<T: Scalar> CooMatrix<T> {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn num_rows(&self) -> usize {
+        return self.rows;
+    }
+
+    pub fn num_cols(&self) -> usize {
+        return self.cols;
+    }
+
+    /// Number of stored entries, counting duplicates separately.
+    pub fn nnz(&self) -> usize {
+        return self.entries.len();
+    }
+
+    /// Adds `value` at `(row, col)`, on top of anything already pushed there.
+    pub fn push(&mut self, row: usize, col: usize, value: T) -> Result<(), MatrixError> {
+        if row >= self.rows || col >= self.cols {
+            return Err(MatrixError::IndexOutOfBounds {
+                row,
+                col,
+                rows: self.rows,
+                cols: self.cols,
+            });
+        }
+
+        self.entries.push((row, col, value));
+        return Ok(());
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        let mut entries = self.entries.clone();
+        entries.sort_by_key(|(row, col, _)| (*row, *col));
+
+        let mut row_ptr = vec![0; self.rows + 1];
+        let mut col_idx: Vec<usize> = Vec::with_capacity(entries.len());
+        let mut values: Vec<T> = Vec::with_capacity(entries.len());
+        let mut last = None;
+
+        for (row, col, value) in entries {
+            if last == Some((row, col)) {
+                if let Some(v) = values.last_mut() {
+                    *v += value;
+                }
+                continue;
+            }
+
+            last = Some((row, col));
+            row_ptr[row + 1] += 1;
+            col_idx.push(col);
+            values.push(value);
+        }
+
+        for i in 0..self.rows {
+            row_ptr[i + 1] += row_ptr[i];
+        }
+
+        return CsrMatrix {
+            rows: self.rows,
+            cols: self.cols,
+            row_ptr,
+            col_idx,
+            values,
+        };
+    }
+}
+
+impl<T: Scalar> CsrMatrix<T> {
+    pub fn identity(n: usize) -> Self {
+        Self {
+            rows: n,
+            cols: n,
+            row_ptr: (0..=n).collect(),
+            col_idx: (0..n).collect(),
+            values: vec![T::one(); n],
+        }
+    }
+
+    /// Stores every non-zero element of a dense matrix.
+    pub fn from_dense(m: &GenericMatrix<T>) -> Self {
+        let mut coo = CooMatrix::new(m.rows, m.cols);
+        for i in 0..m.rows {
+            for j in 0..m.cols {
+                let value = m.get(i, j);
+                if value != T::zero() {
+                    coo.entries.push((i, j, value));
+                }
+            }
+        }
+        return coo.to_csr();
+    }
+
+    pub fn to_dense(&self) -> GenericMatrix<T> {
+        let mut result = GenericMatrix::new(self.rows, self.cols);
+        for i in 0..self.rows {
+            for (j, value) in self.stored(i) {
+                result.put(i, j, value);
+            }
+        }
+        return result;
+    }
+
+    pub fn num_rows(&self) -> usize {
+        return self.rows;
+    }
+
+    pub fn num_cols(&self) -> usize {
+        return self.cols;
+    }
+
+    /// Number of stored entries.
+    pub fn nnz(&self) -> usize {
+        return self.values.len();
+    }
+
+    /// Element at `(row, col)`, zero if nothing is stored there.
+    pub fn at(&self, row: usize, col: usize) -> Result<T, MatrixError> {
+        if row >= self.rows || col >= self.cols {
+            return Err(MatrixError::IndexOutOfBounds {
+                row,
+                col,
+                rows: self.rows,
+                cols: self.cols,
+            });
+        }
+
+        let range = self.row_ptr[row]..self.row_ptr[row + 1];
+        let value = match self.col_idx[range.clone()].binary_search(&col) {
+            Ok(k) => self.values[range.start + k],
+            Err(_) => T::zero(),
+        };
+        return Ok(value);
+    }
+
+    /// Stored `(col, value)` pairs of one row, in column order.
+    pub fn row(&self, row: usize) -> Result<impl Iterator<Item = (usize, T)> + '_, MatrixError> {
+        if row >= self.rows {
+            return Err(MatrixError::IndexOutOfBounds {
+                row,
+                col: 0,
+                rows: self.rows,
+                cols: self.cols,
+            });
+        }
+        return Ok(self.stored(row));
+    }
+
+    /// `row` without the bounds check, for loops over `0..rows`.
+    fn stored(&self, row: usize) -> impl Iterator<Item = (usize, T)> + '_ {
+        let range = self.row_ptr[row]..self.row_ptr[row + 1];
+        return self.col_idx[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.values[range].iter().copied());
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut coo = CooMatrix::new(self.cols, self.rows);
+        for i in 0..self.rows {
+            coo.entries
+                .extend(self.stored(i).map(|(j, value)| (j, i, value)));
+        }
+        return coo.to_csr();
+    }
+
+    /// `A * x` for a vector `x` of length `num_cols`.
+    pub fn multiply_vector(&self, x: &[T]) -> Result<Vec<T>, MatrixError> {
+        if x.len() != self.cols {
+            return Err(MatrixError::DimensionMismatch {
+                left: (self.rows, self.cols),
+                right: (x.len(), 1),
+            });
+        }
+
+        return Ok((0..self.rows)
+            .map(|i| self.stored(i).map(|(j, value)| value * x[j]).sum())
+            .collect());
+    }
+
+    /// `A * m` for a dense `m`, touching only the stored entries of `A`.
+    pub fn multiply_dense(&self, m: &GenericMatrix<T>) -> Result<GenericMatrix<T>, MatrixError> {
+        if self.cols != m.rows {
+            return Err(MatrixError::DimensionMismatch {
+                left: (self.rows, self.cols),
+                right: (m.rows, m.cols),
+            });
+        }
+
+        let mut result = GenericMatrix::new(self.rows, m.cols);
+        for i in 0..self.rows {
+            let out = &mut result.matrix[i * m.cols..(i + 1) * m.cols];
+            for (k, value) in self.stored(i) {
+                let m_row = &m.matrix[k * m.cols..(k + 1) * m.cols];
+                out.iter_mut()
+                    .zip(m_row)
+                    .for_each(|(o, b)| *o += value * *b);
+            }
+        }
+        return Ok(result);
+    }
+}
+
+impl<T: Real> CsrMatrix<T> {
+    /// Conjugate-gradient solve of `A * x = b` for symmetric positive definite
+    /// `A`, starting from zero. Stops once `|b - A * x| <= tolerance * |b|`.
+    ///
+    /// Symmetry is not checked; a direction with non-positive curvature is
+    /// reported as `NotPositiveDefinite`.
+    pub fn solve_cg(
+        &self,
+        b: &[T],
+        tolerance: T,
+        max_iterations: usize,
+    ) -> Result<CgSolution<T>, MatrixError> {
+        if self.rows != self.cols {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows,
+                cols: self.cols,
+            });
+        }
+        if b.len() != self.rows {
+            return Err(MatrixError::DimensionMismatch {
+                left: (self.rows, self.cols),
+                right: (b.len(), 1),
+            });
+        }
+
+        let mut x = vec![T::zero(); self.rows];
+        let mut r = b.to_vec();
+        let mut p = r.clone();
+        let mut rr = dot(&r, &r);
+        let target = tolerance * dot(b, b).sqrt();
+
+        for iteration in 0..=max_iterations {
+            if rr.sqrt() <= target {
+                return Ok(CgSolution {
+                    solution: x,
+                    iterations: iteration,
+                    residual: rr.sqrt(),
+                });
+            }
+            if iteration == max_iterations {
+                break;
+            }
+
+            let ap = self.multiply_vector(&p)?;
+            let curvature = dot(&p, &ap);
+            if curvature <= T::zero() {
+                return Err(MatrixError::NotPositiveDefinite);
+            }
+
+            let alpha = rr / curvature;
+            axpy(&mut x, &p, alpha);
+            axpy(&mut r, &ap, -alpha);
+
+            let rr_next = dot(&r, &r);
+            let beta = rr_next / rr;
+            p.iter_mut().zip(&r).for_each(|(p, r)| *p = *r + beta * *p);
+            rr = rr_next;
+        }
+
+        return Err(MatrixError::NotConverged {
+            iterations: max_iterations,
+        });
+    }
+}
+
+/// `y += a * x`.
+fn axpy<T: Scalar>(y: &mut [T], x: &[T], a: T) {
+    y.iter_mut().zip(x).for_each(|(y, x)| *y += a * *x);
+}
+
+fn dot<T: Scalar>(a: &[T], b: &[T]) -> T {
+    return a.iter().zip(b).map(|(a, b)| *a * *b).sum();
+}
+
+impl<T: Scalar> From<&CsrMatrix<T>> for GenericMatrix<T> {
+    fn from(m: &CsrMatrix<T>) -> GenericMatrix<T> {
+        return m.to_dense();
+    }
+}
+
+impl<T: Scalar> From<&GenericMatrix<T>> for CsrMatrix<T> {
+    fn from(m: &GenericMatrix<T>) -> CsrMatrix<T> {
+        return CsrMatrix::from_dense(m);
+    }
+}
+
+impl<T: Scalar> From<&CooMatrix<T>> for CsrMatrix<T> {
+    fn from(m: &CooMatrix<T>) -> CsrMatrix<T> {
+        return m.to_csr();
+    }
+}
+
+/// Panics on a shape mismatch, like the dense operators.
+impl<T: Scalar> Mul<&GenericMatrix<T>> for &CsrMatrix<T> {
+    type Output = GenericMatrix<T>;
+
+    fn mul(self, rhs: &GenericMatrix<T>) -> GenericMatrix<T> {
+        match self.multiply_dense(rhs) {
+            Ok(m) => return m,
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use std::ops::Index;
+
+use crate::matrix::scalar::{Field, Scalar};
+use crate::matrix::{GenericMatrix, MatrixError};
+
+/// Borrowed, read-only window onto a matrix.
+///
+/// Element `(i, j)` lives at `offset + i * row_stride + j * col_stride` in
+/// the borrowed buffer, so a transposed view only swaps the strides and a
+/// submatrix only moves the offset. Neither copies any elements.
+#[derive(Debug, Clone, Copy)]
+pub struct MatrixView<'a, T> {
+    data: &'a [T],
+    offset: usize,
+    rows: usize,
+    cols: usize,
+    row_stride: usize,
+    col_stride: usize,
+}
+
+/// Iterator over every `stride`-th element of a buffer, used for the rows
+/// and columns of views and the columns of matrices.
+#[derive(Debug, Clone)]
+pub struct Strided<'a, T> {
+    data: &'a [T],
+    next: usize,
+    stride: usize,
+    remaining: usize,
+}
+
+impl<'a, T: Scalar> Iterator for Strided<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let value = self.data[self.next];
+        self.remaining -= 1;
+        self.next += self.stride;
+        return Some(value);
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        return (self.remaining, Some(self.remaining));
+    }
+}
+
+impl<'a, T: Scalar> ExactSizeIterator for Strided<'a, T> {}
+
+impl<'a, T: Scalar> MatrixView<'a, T> {
+    pub fn num_rows(&self) -> usize {
+        return self.rows;
+    }
+
+    pub fn num_cols(&self) -> usize {
+        return self.cols;
+    }
+
+    pub fn at(&self, row: usize, col: usize) -> Result<T, MatrixError> {
+        self.check_bounds(row, col)?;
+        return Ok(self.data[self.position(row, col)]);
+    }
+
+    /// The same elements with rows and columns swapped.
+    pub fn transpose(self) -> MatrixView<'a, T> {
+        return MatrixView {
+            rows: self.cols,
+            cols: self.rows,
+            row_stride: self.col_stride,
+            col_stride: self.row_stride,
+            ..self
+        };
+    }
+
+    /// The `rows` x `cols` block whose top-left element is `(row, col)`.
+    pub fn submatrix(
+        self,
+        row: usize,
+        col: usize,
+        rows: usize,
+        cols: usize,
+    ) -> Result<MatrixView<'a, T>, MatrixError> {
+        let row_end = row.checked_add(rows).filter(|end| *end <= self.rows);
+        let col_end = col.checked_add(cols).filter(|end| *end <= self.cols);
+        if row_end.is_none() || col_end.is_none() {
+            return Err(MatrixError::IndexOutOfBounds {
+                row: row.saturating_add(rows).saturating_sub(1),
+                col: col.saturating_add(cols).saturating_sub(1),
+                rows: self.rows,
+                cols: self.cols,
+            });
+        }
+
+        return Ok(MatrixView {
+            offset: self.offset + row * self.row_stride + col * self.col_stride,
+            rows,
+            cols,
+            ..self
+        });
+    }
+
+    pub fn row(&self, row: usize) -> Result<Strided<'a, T>, MatrixError> {
+        if row >= self.rows {
+            return Err(self.out_of_bounds(row, 0));
+        }
+        return Ok(self.strided(row, 0, self.col_stride, self.cols));
+    }
+
+    pub fn column(&self, col: usize) -> Result<Strided<'a, T>, MatrixError> {
+        if col >= self.cols {
+            return Err(self.out_of_bounds(0, col));
+        }
+        return Ok(self.strided(0, col, self.row_stride, self.rows));
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = Strided<'a, T>> + '_ {
+        return (0..self.rows).map(|i| self.strided(i, 0, self.col_stride, self.cols));
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = Strided<'a, T>> + '_ {
+        return (0..self.cols).map(|j| self.strided(0, j, self.row_stride, self.rows));
+    }
+
+    /// Copies the viewed elements into a new matrix.
+    pub fn to_matrix(self) -> GenericMatrix<T> {
+        return GenericMatrix {
+            rows: self.rows,
+            cols: self.cols,
+            matrix: self.rows().flatten().collect(),
+        };
+    }
+
+    fn position(&self, row: usize, col: usize) -> usize {
+        return self.offset + row * self.row_stride + col * self.col_stride;
+    }
+
+    fn strided(&self, row: usize, col: usize, stride: usize, len: usize) -> Strided<'a, T> {
+        return Strided {
+            data: self.data,
+            next: if len == 0 { 0 } else { self.position(row, col) },
+            stride,
+            remaining: len,
+        };
+    }
+
+    fn check_bounds(&self, row: usize, col: usize) -> Result<(), MatrixError> {
+        if row >= self.rows || col >= self.cols {
+            return Err(self.out_of_bounds(row, col));
+        }
+        return Ok(());
+    }
+
+    fn out_of_bounds(&self, row: usize, col: usize) -> MatrixError {
+        return MatrixError::IndexOutOfBounds {
+            row,
+            col,
+            rows: self.rows,
+            cols: self.cols,
+        };
+    }
+}
+
+impl<'a, T: Scalar> Index<(usize, usize)> for MatrixView<'a, T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        if let Err(e) = self.check_bounds(row, col) {
+            panic!("{}", e);
+        }
+        return &self.data[self.position(row, col)];
+    }
+}
+
+impl<T: Scalar> GenericMatrix<T> {
+    /// The whole matrix as a view.
+    pub fn view(&self) -> MatrixView<'_, T> {
+        return MatrixView {
+            data: &self.matrix,
+            offset: 0,
+            rows: self.rows,
+            cols: self.cols,
+            row_stride: self.cols,
+            col_stride: 1,
+        };
+    }
+
+    /// Lazy transpose that reads this matrix's elements in place.
+    pub fn transpose_view(&self) -> MatrixView<'_, T> {
+        return self.view().transpose();
+    }
+
+    pub fn transpose(&self) -> Self {
+        return self.transpose_view().to_matrix();
+    }
+
+    /// Borrows the `rows` x `cols` block whose top-left element is `(row, col)`.
+    pub fn submatrix(
+        &self,
+        row: usize,
+        col: usize,
+        rows: usize,
+        cols: usize,
+    ) -> Result<MatrixView<'_, T>, MatrixError> {
+        return self.view().submatrix(row, col, rows, cols);
+    }
+
+    pub fn row(&self, row: usize) -> Result<&[T], MatrixError> {
+        if row >= self.rows {
+            return Err(self.view().out_of_bounds(row, 0));
+        }
+        return Ok(&self.matrix[row * self.cols..(row + 1) * self.cols]);
+    }
+
+    pub fn column(&self, col: usize) -> Result<Strided<'_, T>, MatrixError> {
+        return self.view().column(col);
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        return (0..self.rows).map(|i| &self.matrix[i * self.cols..(i + 1) * self.cols]);
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = Strided<'_, T>> + '_ {
+        return (0..self.cols).map(|j| Strided {
+            data: &self.matrix,
+            next: j,
+            stride: self.cols,
+            remaining: self.rows,
+        });
+    }
+
+    /// The matrix with row `row` and column `col` removed.
+    pub fn minor_matrix(&self, row: usize, col: usize) -> Result<Self, MatrixError> {
+        self.check_bounds(row, col)?;
+
+        let matrix = self
+            .rows()
+            .enumerate()
+            .filter(|(i, _)| *i != row)
+            .flat_map(|(_, r)| {
+                r.iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != col)
+                    .map(|(_, x)| *x)
+            })
+            .collect();
+
+        return Ok(Self {
+            rows: self.rows - 1,
+            cols: self.cols - 1,
+            matrix,
+        });
+    }
+
+    /// `[self | m]`: the columns of `m` appended to the right.
+    pub fn hstack(&self, m: &Self) -> Result<Self, MatrixError> {
+        if self.rows != m.rows {
+            return Err(self.mismatch(m));
+        }
+
+        let matrix = self
+            .rows()
+            .zip(m.rows())
+            .flat_map(|(a, b)| a.iter().chain(b).copied())
+            .collect();
+
+        return Ok(Self {
+            rows: self.rows,
+            cols: self.cols + m.cols,
+            matrix,
+        });
+    }
+
+    /// The rows of `m` appended below.
+    pub fn vstack(&self, m: &Self) -> Result<Self, MatrixError> {
+        if self.cols != m.cols {
+            return Err(self.mismatch(m));
+        }
+
+        return Ok(Self {
+            rows: self.rows + m.rows,
+            cols: self.cols,
+            matrix: self.matrix.iter().chain(&m.matrix).copied().collect(),
+        });
+    }
+}
+
+impl<T: Field> GenericMatrix<T> {
+    /// Determinant of the matrix with row `row` and column `col` removed.
+    pub fn minor(&self, row: usize, col: usize) -> Result<T, MatrixError> {
+        self.check_square()?;
+        return self.minor_matrix(row, col)?.determinant();
+    }
+
+    /// The minor with the checkerboard sign `(-1)^(row + col)` applied.
+    pub fn cofactor(&self, row: usize, col: usize) -> Result<T, MatrixError> {
+        let minor = self.minor(row, col)?;
+        if (row + col) % 2 == 1 {
+            return Ok(-minor);
+        }
+        return Ok(minor);
+    }
+
+    pub fn cofactor_matrix(&self) -> Result<Self, MatrixError> {
+        self.check_square()?;
+
+        let mut result = Self::new(self.rows, self.cols);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                result.put(i, j, self.cofactor(i, j)?);
+            }
+        }
+        return Ok(result);
+    }
+
+    /// Transposed cofactor matrix, so that `A * adj(A) = det(A) * I`.
+    pub fn adjugate(&self) -> Result<Self, MatrixError> {
+        return Ok(self.cofactor_matrix()?.transpose());
+    }
+}
@@ -0,0 +1,401 @@
+use core::fmt;
+use std::ops::{Mul, MulAssign, Neg};
+
+use crate::matrix::fixed::Matrix4;
+use crate::matrix::MatrixError;
+use crate::transformations::{translate, RotationAxis};
+use crate::vector::Vec3;
+
+/// Largest orthogonality error and determinant error `from_matrix` accepts.
+const ROTATION_TOLERANCE: f32 = 1e-3;
+
+/// Above this cosine `slerp` falls back to `nlerp`, which is indistinguishable
+/// there and does not divide by a vanishing sine.
+const SLERP_THRESHOLD: f32 = 0.9995;
+
+/// Below this cosine of the middle Euler angle the outer two angles are
+/// treated as turning about the same axis.
+const GIMBAL_LOCK_COSINE: f32 = 1e-3;
+
+/// Rotation stored as `w + xi + yj + zk`. Rotations are unit quaternions;
+/// `q` and `-q` describe the same rotation.
+///
+/// Positive angles turn counter-clockwise about the axis when looking back
+/// along it (right-hand rule). `transformations::rotate` follows that rule
+/// for X and Z but turns the other way about Y.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion {
+    pub w: f32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+/// Order in which Euler angles are applied. The first axis is applied first,
+/// and `angles[i]` of `from_euler` is the angle about the i-th axis named.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EulerOrder {
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+}
+
+impl EulerOrder {
+    pub const ALL: [EulerOrder; 6] = [
+        EulerOrder::XYZ,
+        EulerOrder::XZY,
+        EulerOrder::YXZ,
+        EulerOrder::YZX,
+        EulerOrder::ZXY,
+        EulerOrder::ZYX,
+    ];
+
+    pub fn axes(self) -> [RotationAxis; 3] {
+        use RotationAxis::{X, Y, Z};
+
+        return match self {
+            EulerOrder::XYZ => [X, Y, Z],
+            EulerOrder::XZY => [X, Z, Y],
+            EulerOrder::YXZ => [Y, X, Z],
+            EulerOrder::YZX => [Y, Z, X],
+            EulerOrder::ZXY => [Z, X, Y],
+            EulerOrder::ZYX => [Z, Y, X],
+        };
+    }
+}
+
+fn axis_index(axis: RotationAxis) -> usize {
+    return match axis {
+        RotationAxis::X => 0,
+        RotationAxis::Y => 1,
+        RotationAxis::Z => 2,
+    };
+}
+
+fn axis_vector(axis: RotationAxis) -> Vec3 {
+    return match axis {
+        RotationAxis::X => Vec3::new(1.0, 0.0, 0.0),
+        RotationAxis::Y => Vec3::new(0.0, 1.0, 0.0),
+        RotationAxis::Z => Vec3::new(0.0, 0.0, 1.0),
+    };
+}
+
+impl Quaternion {
+    pub fn new(w: f32, x: f32, y: f32, z: f32) -> Self {
+        Self { w, x, y, z }
+    }
+
+    pub fn identity() -> Self {
+        return Self::new(1.0, 0.0, 0.0, 0.0);
+    }
+
+    /// Rotation by `angle` radians about `axis`, which need not be unit
+    /// length. A zero axis gives the identity.
+    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Self {
+        let axis = match axis.normalize() {
+            Some(axis) => axis,
+            None => return Self::identity(),
+        };
+        let (sin, cos) = (angle / 2.0).sin_cos();
+
+        return Self::new(cos, axis.x * sin, axis.y * sin, axis.z * sin);
+    }
+
+    /// Unit axis and angle in `[0, pi]`. The identity reports the X axis.
+    pub fn to_axis_angle(self) -> (Vec3, f32) {
+        let q = self.normalize().unwrap_or_else(Self::identity);
+        let q = if q.w < 0.0 { -q } else { q };
+        let v = q.vector();
+
+        let angle = 2.0 * v.length().atan2(q.w);
+        let axis = v.normalize().unwrap_or(Vec3::new(1.0, 0.0, 0.0));
+
+        return (axis, angle);
+    }
+
+    /// Applies `angles[0]` about the first axis of `order`, then `angles[1]`
+    /// about the second and `angles[2]` about the third, all about the fixed
+    /// world axes.
+    pub fn from_euler(order: EulerOrder, angles: [f32; 3]) -> Self {
+        let axes = order.axes();
+
+        return (0..3)
+            .map(|i| Self::from_axis_angle(axis_vector(axes[i]), angles[i]))
+            .fold(Self::identity(), |q, r| q.then(r));
+    }
+
+    /// Angles that `from_euler` turns back into this rotation. The middle
+    /// angle lies in `[-pi/2, pi/2]`. At gimbal lock only the sum or
+    /// difference of the outer angles is defined, and the first is set to
+    /// zero.
+    pub fn to_euler(self, order: EulerOrder) -> [f32; 3] {
+        let [i, j, k] = order.axes().map(axis_index);
+        let m = self.normalize().unwrap_or_else(Self::identity).rotation();
+
+        // Cyclic orders (XYZ, YZX, ZXY) have positive parity.
+        let s = if (j + 3 - i) % 3 == 1 { 1.0 } else { -1.0 };
+
+        let cos_b = m[k][j].hypot(m[k][k]);
+        let b = (-s * m[k][i]).atan2(cos_b);
+
+        if cos_b < GIMBAL_LOCK_COSINE {
+            let c = (-s * m[i][j]).atan2(m[j][j]);
+            return [0.0, b, c];
+        }
+
+        let a = (s * m[k][j]).atan2(m[k][k]);
+        let c = (s * m[j][i]).atan2(m[i][i]);
+
+        return [a, b, c];
+    }
+
+    /// The rotation part of a transform. Translation is ignored; scale,
+    /// shear, reflection and perspective are rejected.
+    pub fn from_matrix(matrix: &Matrix4) -> Result<Self, MatrixError> {
+        let rows = (0..3)
+            .map(|r| Vec3::new(matrix[(r, 0)], matrix[(r, 1)], matrix[(r, 2)]))
+            .collect::<Vec<Vec3>>();
+
+        let perspective = (0..3).any(|r| matrix[(r, 3)] != 0.0) || matrix[(3, 3)] != 1.0;
+        let orthogonal = (0..3).all(|a| {
+            (0..3).all(|b| {
+                let expected = if a == b { 1.0 } else { 0.0 };
+                (rows[a].dot(rows[b]) - expected).abs() <= ROTATION_TOLERANCE
+            })
+        });
+        let determinant = rows[0].cross(rows[1]).dot(rows[2]);
+
+        if perspective || !orthogonal || (determinant - 1.0).abs() > ROTATION_TOLERANCE {
+            return Err(MatrixError::NotRotation);
+        }
+
+        // Row vectors: the column-vector rotation is the transpose.
+        let m = |r: usize, c: usize| matrix[(c, r)];
+        let trace = m(0, 0) + m(1, 1) + m(2, 2);
+
+        // Shepperd's method: divide by the largest of the four candidates.
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self::new(
+                s / 4.0,
+                (m(2, 1) - m(1, 2)) / s,
+                (m(0, 2) - m(2, 0)) / s,
+                (m(1, 0) - m(0, 1)) / s,
+            )
+        } else if m(0, 0) > m(1, 1) && m(0, 0) > m(2, 2) {
+            let s = (1.0 + m(0, 0) - m(1, 1) - m(2, 2)).sqrt() * 2.0;
+            Self::new(
+                (m(2, 1) - m(1, 2)) / s,
+                s / 4.0,
+                (m(0, 1) + m(1, 0)) / s,
+                (m(0, 2) + m(2, 0)) / s,
+            )
+        } else if m(1, 1) > m(2, 2) {
+            let s = (1.0 + m(1, 1) - m(0, 0) - m(2, 2)).sqrt() * 2.0;
+            Self::new(
+                (m(0, 2) - m(2, 0)) / s,
+                (m(0, 1) + m(1, 0)) / s,
+                s / 4.0,
+                (m(1, 2) + m(2, 1)) / s,
+            )
+        } else {
+            let s = (1.0 + m(2, 2) - m(0, 0) - m(1, 1)).sqrt() * 2.0;
+            Self::new(
+                (m(1, 0) - m(0, 1)) / s,
+                (m(0, 2) + m(2, 0)) / s,
+                (m(1, 2) + m(2, 1)) / s,
+                s / 4.0,
+            )
+        };
+
+        return Ok(q.normalize().unwrap_or_else(Self::identity));
+    }
+
+    /// Rotation matrix for row vectors, matching `transformations::rotate`:
+    /// `p * q.to_matrix()` equals `q.rotate_vector(p)`.
+    pub fn to_matrix(self) -> Matrix4 {
+        let m = self.normalize().unwrap_or_else(Self::identity).rotation();
+
+        return Matrix4::from_array([
+            [m[0][0], m[1][0], m[2][0], 0.0],
+            [m[0][1], m[1][1], m[2][1], 0.0],
+            [m[0][2], m[1][2], m[2][2], 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+    }
+
+    /// Rotation about `center` instead of the origin.
+    pub fn to_matrix_about(self, center: Vec3) -> Matrix4 {
+        let a = translate(-center.x, -center.y, -center.z);
+        let b = translate(center.x, center.y, center.z);
+
+        return a * self.to_matrix() * b;
+    }
+
+    /// Rotates by `self` and then by `next`, the order row vector matrices
+    /// compose in: `a.then(b).to_matrix() == a.to_matrix() * b.to_matrix()`.
+    /// This is the Hamilton product `next * self`.
+    pub fn then(self, next: Quaternion) -> Quaternion {
+        return next * self;
+    }
+
+    pub fn vector(self) -> Vec3 {
+        return Vec3::new(self.x, self.y, self.z);
+    }
+
+    pub fn dot(self, q: Quaternion) -> f32 {
+        return self.w * q.w + self.x * q.x + self.y * q.y + self.z * q.z;
+    }
+
+    pub fn norm(self) -> f32 {
+        return self.dot(self).sqrt();
+    }
+
+    /// Unit quaternion in the same direction, or `None` for zero.
+    pub fn normalize(self) -> Option<Quaternion> {
+        let norm = self.norm();
+        if norm == 0.0 || !norm.is_finite() {
+            return None;
+        }
+        return Some(self.scale(1.0 / norm));
+    }
+
+    pub fn conjugate(self) -> Quaternion {
+        return Self::new(self.w, -self.x, -self.y, -self.z);
+    }
+
+    /// Multiplicative inverse, or `None` for zero. For unit quaternions this
+    /// is the conjugate, the opposite rotation.
+    pub fn inverse(self) -> Option<Quaternion> {
+        let norm_squared = self.dot(self);
+        if norm_squared == 0.0 || !norm_squared.is_finite() {
+            return None;
+        }
+        return Some(self.conjugate().scale(1.0 / norm_squared));
+    }
+
+    /// Rotates `v` by this quaternion, which should be unit length.
+    pub fn rotate_vector(self, v: Vec3) -> Vec3 {
+        let u = self.vector();
+        let t = u.cross(v) * 2.0;
+
+        return v + t * self.w + u.cross(t);
+    }
+
+    /// Normalised linear interpolation along the shorter arc. Cheaper than
+    /// `slerp` but the angular speed is not constant.
+    pub fn nlerp(self, q: Quaternion, t: f32) -> Quaternion {
+        let q = if self.dot(q) < 0.0 { -q } else { q };
+        let blend = self.scale(1.0 - t).add(q.scale(t));
+
+        return blend.normalize().unwrap_or(self);
+    }
+
+    /// Spherical linear interpolation along the shorter arc with constant
+    /// angular speed. `t = 0` gives `self` and `t = 1` gives `q`.
+    pub fn slerp(self, q: Quaternion, t: f32) -> Quaternion {
+        let cos = self.dot(q);
+        let (q, cos) = if cos < 0.0 { (-q, -cos) } else { (q, cos) };
+
+        if cos > SLERP_THRESHOLD {
+            return self.nlerp(q, t);
+        }
+
+        let theta = cos.clamp(-1.0, 1.0).acos();
+        let sin = theta.sin();
+        let a = ((1.0 - t) * theta).sin() / sin;
+        let b = (t * theta).sin() / sin;
+
+        return self.scale(a).add(q.scale(b));
+    }
+
+    /// Column-vector rotation matrix of a unit quaternion.
+    fn rotation(self) -> [[f32; 3]; 3] {
+        let Quaternion { w, x, y, z } = self;
+
+        return [
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ],
+        ];
+    }
+
+    fn scale(self, s: f32) -> Quaternion {
+        return Self::new(self.w * s, self.x * s, self.y * s, self.z * s);
+    }
+
+    fn add(self, q: Quaternion) -> Quaternion {
+        return Self::new(self.w + q.w, self.x + q.x, self.y + q.y, self.z + q.z);
+    }
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        return Self::identity();
+    }
+}
+
+/// The Hamilton product: `a * b` rotates by `b` first and then by `a`. See
+/// `then` for the row vector order.
+impl Mul for Quaternion {
+    type Output = Quaternion;
+
+    fn mul(self, q: Quaternion) -> Quaternion {
+        return Self::new(
+            self.w * q.w - self.x * q.x - self.y * q.y - self.z * q.z,
+            self.w * q.x + self.x * q.w + self.y * q.z - self.z * q.y,
+            self.w * q.y - self.x * q.z + self.y * q.w + self.z * q.x,
+            self.w * q.z + self.x * q.y - self.y * q.x + self.z * q.w,
+        );
+    }
+}
+
+impl MulAssign for Quaternion {
+    fn mul_assign(&mut self, rhs: Quaternion) {
+        *self = *self * rhs;
+    }
+}
+
+impl Neg for Quaternion {
+    type Output = Quaternion;
+
+    fn neg(self) -> Quaternion {
+        return self.scale(-1.0);
+    }
+}
+
+impl From<Quaternion> for Matrix4 {
+    fn from(q: Quaternion) -> Self {
+        return q.to_matrix();
+    }
+}
+
+impl TryFrom<&Matrix4> for Quaternion {
+    type Error = MatrixError;
+
+    fn try_from(matrix: &Matrix4) -> Result<Self, Self::Error> {
+        return Quaternion::from_matrix(matrix);
+    }
+}
+
+impl fmt::Display for Quaternion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "({} + {}i + {}j + {}k)", self.w, self.x, self.y, self.z);
+    }
+}
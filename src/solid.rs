@@ -3,16 +3,35 @@ pub mod pyramid;
 pub mod square;
 pub mod triangle;
 
+use std::fmt;
 use std::rc::Rc;
+use std::thread;
 
 use nannou::geom::{pt2, Point2};
 
+use crate::matrix::fixed::Matrix4;
 use crate::matrix::Matrix;
+use crate::quaternion::Quaternion;
+use crate::vector::{Vec3, Vec4};
+
+/// Batches at least this large are multiplied on every available core.
+const PARALLEL_BATCH: usize = 4096;
 
 pub trait GetLines {
     fn get_lines(&self) -> Vec<Line<&Point>>;
 }
 
+/// Solids that move their own vertices.
+#[allow(dead_code)]
+pub trait Transformable {
+    fn apply_matrix(&mut self, matrix: &Matrix4);
+
+    /// Rotates every vertex about `center`.
+    fn apply_quaternion(&mut self, rotation: Quaternion, center: &Point) {
+        self.apply_matrix(&rotation.to_matrix_about(Vec3::from(center)));
+    }
+}
+
 pub struct Point {
     pub x: f32,
     pub y: f32,
@@ -24,11 +43,80 @@ pub struct Line<P> {
     pub b: Rc<P>,
 }
 
+/// A vertex after the perspective divide. `depth` is the normalised device
+/// depth and `w` the clip-space divisor it came from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScreenVertex {
+    pub position: Point2,
+    pub depth: f32,
+    pub w: f32,
+}
+
+#[allow(dead_code)]
 pub struct Scene<P> {
     pub num_lines: usize,
     pub lines: Vec<Line<P>>,
 }
 
+/// Symmetric perspective frustum: `fov_y` is the full vertical angle in
+/// radians, `aspect` is width over height, and `0 < near < far` are the
+/// distances to the clip planes along the view direction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Perspective {
+    pub fov_y: f32,
+    pub aspect: f32,
+    pub near: f32,
+    pub far: f32,
+}
+
+/// Box seen by a parallel projection: `width` by `height` centred on the
+/// view axis, between `near` and `far` along it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Orthographic {
+    pub width: f32,
+    pub height: f32,
+    pub near: f32,
+    pub far: f32,
+}
+
+/// How the scene is mapped to clip space.
+///
+/// The parallel modes map their `Orthographic` box onto the canonical clip
+/// volume with `w = 1`. Axonometric and oblique modes fix the view direction
+/// themselves and only keep the eye's distance from the origin; the world is
+/// z-up and the camera looks at the origin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum Projection {
+    /// The original projection: x and y are scaled by `view_distance /
+    /// screen_size` and divided by depth, which is left unnormalised.
+    Legacy,
+    /// Maps the frustum onto the canonical clip volume `-w <= x, y, z <= w`,
+    /// with depth -1 at the near plane and 1 at the far plane.
+    Perspective(Perspective),
+    /// Parallel projection along the eye's view direction.
+    Orthographic(Orthographic),
+    /// All three axes equally foreshortened.
+    Isometric(Orthographic),
+    /// The two horizontal axes equally foreshortened, seen from `elevation`
+    /// radians above the ground plane.
+    Dimetric {
+        volume: Orthographic,
+        elevation: f32,
+    },
+    /// Seen from `azimuth` radians around the z axis, measured from x, and
+    /// `elevation` radians above the ground plane.
+    Trimetric {
+        volume: Orthographic,
+        azimuth: f32,
+        elevation: f32,
+    },
+    /// Front view with receding lines drawn full length at `angle` radians.
+    Cavalier { volume: Orthographic, angle: f32 },
+    /// Front view with receding lines drawn half length at `angle` radians.
+    Cabinet { volume: Orthographic, angle: f32 },
+}
+
 pub struct ScreenParameter {
     eye_coordinates: [f32; 3],
     view_distance: f32,
@@ -37,6 +125,13 @@ pub struct ScreenParameter {
     vcy: f32,
     vsx: f32,
     vsy: f32,
+    projection: Projection,
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "({}, {}, {})", self.x, self.y, self.z);
+    }
 }
 
 impl Point {
@@ -52,36 +147,88 @@ impl Point {
         };
     }
 
-    pub fn apply_matrix(&self, matrix: &Matrix) -> Point {
-        let mut a = Matrix::new(1, 4);
-        a.set(0, 0, self.x);
-        a.set(0, 1, self.y);
-        a.set(0, 2, self.z);
-        a.set(0, 3, 1.0);
+    /// The point as homogeneous coordinates, with `w = 1`.
+    pub fn to_homogeneous(&self) -> Vec4 {
+        return Vec3::from(self).extend(1.0);
+    }
 
-        let b = a.matrix_multiply(matrix).unwrap();
+    /// Transforms the point and divides by the resulting `w`. Affine
+    /// matrices leave `w = 1`, so the divide is exact for them; a `w` of
+    /// zero gives non-finite coordinates.
+    pub fn apply_matrix(&self, matrix: &Matrix4) -> Point {
+        return Point::from(divide(self.apply_matrix_homogeneous(matrix)));
+    }
 
-        return Point {
-            x: b.at(0, 0),
-            y: b.at(0, 1),
-            z: b.at(0, 2),
+    /// Transforms the point and keeps `w`, as needed before clipping.
+    pub fn apply_matrix_homogeneous(&self, matrix: &Matrix4) -> Vec4 {
+        return self.to_homogeneous() * *matrix;
+    }
+
+    /// Rotates the point about the origin.
+    pub fn apply_quaternion(&self, rotation: Quaternion) -> Point {
+        return Point::from(rotation.rotate_vector(Vec3::from(self)));
+    }
+
+    /// Transforms every point with one N x 4 by 4 x 4 product. Each result is
+    /// bit-identical to calling `apply_matrix` on that point, since both sum
+    /// from zero in the same order.
+    pub fn apply_matrix_batch(points: &[&Point], matrix: &Matrix4) -> Vec<Point> {
+        return Point::apply_matrix_batch_homogeneous(points, matrix)
+            .into_iter()
+            .map(|v| Point::from(divide(v)))
+            .collect();
+    }
+
+    /// Batched `apply_matrix_homogeneous`.
+    pub fn apply_matrix_batch_homogeneous(points: &[&Point], matrix: &Matrix4) -> Vec<Vec4> {
+        let data = points
+            .iter()
+            .flat_map(|p| [p.x, p.y, p.z, 1.0])
+            .collect::<Vec<f32>>();
+        let a = Matrix::from_row_major(points.len(), 4, data).expect("four coordinates per point");
+        let b = matrix.to_matrix();
+
+        let product = if points.len() >= PARALLEL_BATCH {
+            let threads = thread::available_parallelism().map_or(1, |n| n.get());
+            a.matrix_multiply_parallel(&b, threads)
+        } else {
+            a.matrix_multiply_blocked(&b)
         };
+
+        return product
+            .expect("N x 4 by 4 x 4 shapes always match")
+            .as_slice()
+            .chunks_exact(4)
+            .map(|row| Vec4::new(row[0], row[1], row[2], row[3]))
+            .collect();
     }
+}
+
+fn divide(v: Vec4) -> Vec3 {
+    return v.truncate() / v.w;
+}
 
-    pub fn to_screen_point(&self, vsx: f32, vsy: f32, vcx: f32, vcy: f32) -> Point2 {
-        let x = ((self.x / self.z) * vsx) + vcx;
-        let y = ((self.y / self.z) * vsy) + vcy;
+impl ScreenVertex {
+    /// Perspective divide of a clip-space vertex, then the viewport mapping
+    /// from normalised device coordinates to the window.
+    pub fn from_clip(v: Vec4, vsx: f32, vsy: f32, vcx: f32, vcy: f32) -> ScreenVertex {
+        let ndc = divide(v);
 
-        return pt2(x, y);
+        return ScreenVertex {
+            position: pt2(ndc.x * vsx + vcx, ndc.y * vsy + vcy),
+            depth: ndc.z,
+            w: v.w,
+        };
     }
 
-    pub fn to_string(&self) -> String {
-        return format!(
-            "({}, {}, {})",
-            self.x.to_string(),
-            self.y.to_string(),
-            self.z.to_string()
-        );
+    /// Maps a parameter `t` measured along the screen-space segment from
+    /// `self` to `b` onto the matching parameter along the original 3D
+    /// segment. Attributes interpolated with the result are
+    /// perspective-correct; `depth` itself is already linear in screen space.
+    #[allow(dead_code)]
+    pub fn perspective_parameter(&self, b: &ScreenVertex, t: f32) -> f32 {
+        let (inv_a, inv_b) = (1.0 / self.w, 1.0 / b.w);
+        return t * inv_b / ((1.0 - t) * inv_a + t * inv_b);
     }
 }
 
@@ -95,6 +242,7 @@ impl<P> Line<P> {
 }
 
 impl<P> Scene<P> {
+    #[allow(dead_code)]
     pub fn new() -> Self {
         Self {
             num_lines: 0,
@@ -103,6 +251,30 @@ impl<P> Scene<P> {
     }
 }
 
+impl Perspective {
+    #[allow(dead_code)]
+    pub fn new(fov_y: f32, aspect: f32, near: f32, far: f32) -> Self {
+        Self {
+            fov_y,
+            aspect,
+            near,
+            far,
+        }
+    }
+}
+
+impl Orthographic {
+    #[allow(dead_code)]
+    pub fn new(width: f32, height: f32, near: f32, far: f32) -> Self {
+        Self {
+            width,
+            height,
+            near,
+            far,
+        }
+    }
+}
+
 impl ScreenParameter {
     pub fn new(
         eye_coordinates: [f32; 3],
@@ -121,8 +293,16 @@ impl ScreenParameter {
             vsy,
             vcx,
             vcy,
+            projection: Projection::Legacy,
         }
     }
+
+    #[allow(dead_code)]
+    pub fn with_projection(mut self, projection: Projection) -> Self {
+        self.projection = projection;
+        return self;
+    }
+
     pub fn get_eye_coordinates(&self) -> [f32; 3] {
         return self.eye_coordinates;
     }
@@ -133,4 +313,8 @@ impl ScreenParameter {
     pub fn get_view_angle(&self) -> f32 {
         return self.view_distance / self.screen_size;
     }
+
+    pub fn get_projection(&self) -> Projection {
+        return self.projection;
+    }
 }
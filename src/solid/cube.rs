@@ -1,4 +1,4 @@
-use crate::shape::*;
+use crate::solid::*;
 #[allow(dead_code)]
 pub struct Cube<'a> {
     side_length: f32,
@@ -9,7 +9,7 @@ pub struct Cube<'a> {
 impl GetLines for Cube<'_> {
     fn get_lines(&self) -> Vec<Line<&Point>> {
         let mut lines = Vec::new();
-        (0..4).into_iter().for_each(|i| {
+        (0..4).for_each(|i| {
             lines.push(Line::new(&self.points[i], &self.points[i + 4]));
             lines.push(Line::new(&self.points[i], &self.points[(i + 1) % 4]));
             lines.push(Line::new(
@@ -21,6 +21,13 @@ impl GetLines for Cube<'_> {
     }
 }
 
+impl Transformable for Cube<'_> {
+    fn apply_matrix(&mut self, matrix: &Matrix4) {
+        let points = self.points.iter().collect::<Vec<&Point>>();
+        self.points = Point::apply_matrix_batch(&points, matrix);
+    }
+}
+
 #[allow(dead_code)]
 impl<'a> Cube<'a> {
     pub fn new(side_length: f32, offset: &'a Point) -> Self {
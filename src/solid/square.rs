@@ -1,5 +1,6 @@
-use crate::shape::*;
+use crate::solid::*;
 
+#[allow(dead_code)]
 pub struct Square<'a> {
     side_length: f32,
     offset: &'a Point,
@@ -9,7 +10,6 @@ pub struct Square<'a> {
 impl<'a> GetLines for Square<'a> {
     fn get_lines(&self) -> Vec<Line<&Point>> {
         let lines = (0..4)
-            .into_iter()
             .map(|i| Line::new(&self.points[i], &self.points[(i + 1) % 4]))
             .collect::<Vec<Line<&Point>>>();
 
@@ -17,6 +17,13 @@ impl<'a> GetLines for Square<'a> {
     }
 }
 
+impl Transformable for Square<'_> {
+    fn apply_matrix(&mut self, matrix: &Matrix4) {
+        let points = self.points.iter().collect::<Vec<&Point>>();
+        self.points = Point::apply_matrix_batch(&points, matrix);
+    }
+}
+
 impl<'a> Square<'a> {
     pub fn new(side_length: f32, offset: &'a Point) -> Self {
         let (x, y) = (side_length + offset.x, side_length + offset.y);
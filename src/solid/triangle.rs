@@ -1,6 +1,12 @@
-use crate::shape::*;
+use crate::solid::*;
 
 #[allow(dead_code)]
 pub struct Triangle {
     points: [Point; 3],
 }
+
+impl Transformable for Triangle {
+    fn apply_matrix(&mut self, matrix: &Matrix4) {
+        self.points = self.points.each_ref().map(|p| p.apply_matrix(matrix));
+    }
+}
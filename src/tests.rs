@@ -1 +1,22 @@
+mod builder_test;
+mod clipping_test;
+mod compare_test;
+mod decompose_test;
+mod decomposition_test;
+mod eigen_test;
+mod fixed_test;
+mod format_test;
+mod functions_test;
+mod helpers;
+mod io_test;
+mod matrix_bench;
 mod matrix_test;
+mod ops_test;
+mod pipeline_test;
+mod projection_test;
+mod quaternion_test;
+mod scalar_test;
+mod sparse_test;
+mod transformations_test;
+mod vector_test;
+mod view_test;
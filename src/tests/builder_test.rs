@@ -0,0 +1,131 @@
+use std::f32::consts::FRAC_PI_2;
+
+use crate::matrix::compare::Tolerance;
+use crate::matrix::fixed::Matrix4;
+use crate::quaternion::Quaternion;
+use crate::solid::Point;
+use crate::tests::helpers::close;
+use crate::transformations::builder::{Operation, Transform};
+use crate::transformations::stack::MatrixStack;
+use crate::transformations::{
+    basic_scale, reflect, rotate, rotate_about_axis, scale, shear, translate, RotationAxis,
+};
+use crate::vector::{Plane, Vec3};
+
+const TOLERANCE: Tolerance<f32> = Tolerance::Absolute(1e-5);
+
+#[test]
+fn builder_matches_free_functions() {
+    let axis = Vec3::new(1.0, 1.0, 0.0);
+    let center = Vec3::new(1.0, 2.0, 3.0);
+    let plane = Plane::new(0.0, 1.0, 0.0, -1.0).unwrap();
+
+    let built = Transform::new()
+        .translate(1.0, -2.0, 0.5)
+        .scale(2.0, 3.0, 4.0)
+        .scale_about(0.5, 0.5, 0.5, center)
+        .rotate(RotationAxis::Y, 0.3)
+        .rotate_about(axis, 1.2, center)
+        .shear(0.1, 0.0, 0.0, 0.2, 0.0, 0.0)
+        .reflect(plane)
+        .build();
+
+    let expected = translate(1.0, -2.0, 0.5)
+        * basic_scale(2.0, 3.0, 4.0)
+        * scale(0.5, 0.5, 0.5, center.x, center.y, center.z)
+        * rotate(RotationAxis::Y, 0.3, 0.0, 0.0, 0.0)
+        * rotate_about_axis(axis, 1.2, center.x, center.y, center.z)
+        * shear(0.1, 0.0, 0.0, 0.2, 0.0, 0.0)
+        * reflect(&plane);
+
+    assert!(built.approx_eq(&expected, TOLERANCE));
+    assert_eq!(Transform::new().build(), Matrix4::identity());
+}
+
+#[test]
+fn steps_apply_in_the_order_written() {
+    let p = Point::from_f32(1.0, 0.0, 0.0);
+
+    // Move out, then turn: the point swings round the origin.
+    let turn_after = Transform::new()
+        .translate(1.0, 0.0, 0.0)
+        .rotate(RotationAxis::Z, FRAC_PI_2);
+    assert!(close(
+        Vec3::from(turn_after.apply(&p)),
+        Vec3::new(0.0, 2.0, 0.0)
+    ));
+
+    // Turn, then move out.
+    let turn_first = Transform::new()
+        .rotate(RotationAxis::Z, FRAC_PI_2)
+        .translate(1.0, 0.0, 0.0);
+    assert!(close(
+        Vec3::from(turn_first.apply(&p)),
+        Vec3::new(1.0, 1.0, 0.0)
+    ));
+}
+
+#[test]
+fn builder_records_operations() {
+    let q = Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), 0.5);
+    let transform = Transform::new()
+        .translate(1.0, 2.0, 3.0)
+        .rotate_by(q, Vec3::zero())
+        .matrix(basic_scale(2.0, 2.0, 2.0));
+
+    assert_eq!(
+        transform.operations(),
+        &[
+            Operation::Translate(Vec3::new(1.0, 2.0, 3.0)),
+            Operation::Quaternion {
+                rotation: q,
+                center: Vec3::zero()
+            },
+            Operation::Matrix(basic_scale(2.0, 2.0, 2.0)),
+        ]
+    );
+
+    let expected = translate(1.0, 2.0, 3.0) * q.to_matrix() * basic_scale(2.0, 2.0, 2.0);
+    assert!(Matrix4::from(&transform).approx_eq(&expected, TOLERANCE));
+}
+
+#[test]
+fn stack_composes_children_inside_parents() {
+    let body = translate(10.0, 0.0, 0.0);
+    let wheel = Transform::new()
+        .rotate(RotationAxis::Z, FRAC_PI_2)
+        .translate(0.0, -1.0, 0.0)
+        .build();
+
+    let mut stack = MatrixStack::new();
+    stack.transform(&body);
+    stack.push();
+    stack.transform(&wheel);
+    assert_eq!(stack.depth(), 1);
+
+    let p = Point::from_f32(1.0, 0.0, 0.0);
+    assert!(stack.top().approx_eq(&(wheel * body), TOLERANCE));
+    assert!(close(
+        Vec3::from(stack.apply(&p)),
+        Vec3::new(10.0, 0.0, 0.0)
+    ));
+
+    assert!(stack.pop().unwrap().approx_eq(&(wheel * body), TOLERANCE));
+    assert_eq!(stack.top(), body);
+    assert_eq!(stack.depth(), 0);
+}
+
+#[test]
+fn stack_pop_without_push_keeps_the_matrix() {
+    let mut stack = MatrixStack::default();
+    stack.load(translate(1.0, 2.0, 3.0));
+
+    assert_eq!(stack.pop(), None);
+    assert_eq!(stack.top(), translate(1.0, 2.0, 3.0));
+
+    stack.push();
+    stack.load_identity();
+    assert_eq!(stack.top(), Matrix4::identity());
+    assert_eq!(stack.pop(), Some(Matrix4::identity()));
+    assert_eq!(stack.top(), translate(1.0, 2.0, 3.0));
+}
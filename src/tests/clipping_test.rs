@@ -0,0 +1,134 @@
+use crate::drawline::{clip_line, clip_scene, create_screen_scene, get_perspective_matrix};
+use crate::solid::cube::Cube;
+use crate::solid::{GetLines, Line, Perspective, Point, Projection, Scene, ScreenParameter};
+use crate::vector::Vec4;
+
+fn inside(v: Vec4) -> bool {
+    let slack = 1e-4 * v.w.abs().max(1.0);
+    return v.w > 0.0
+        && v.x.abs() <= v.w + slack
+        && v.y.abs() <= v.w + slack
+        && v.z.abs() <= v.w + slack;
+}
+
+fn perspective() -> Perspective {
+    return Perspective::new(1.2, 1.0, 1.0, 100.0);
+}
+
+/// Clip coordinates of a view-space point.
+fn clip(x: f32, y: f32, z: f32) -> Vec4 {
+    return Point::from_f32(x, y, z)
+        .apply_matrix_homogeneous(&get_perspective_matrix(&perspective()));
+}
+
+#[test]
+fn lines_inside_are_unchanged() {
+    let (a, b) = (clip(-1.0, 0.5, 5.0), clip(2.0, -1.0, 20.0));
+    assert_eq!(clip_line(a, b), Some((a, b)));
+}
+
+#[test]
+fn lines_outside_are_dropped() {
+    // Left of the frustum, beyond the far plane, and between the eye and the
+    // near plane.
+    for (a, b) in [
+        (clip(-50.0, 0.0, 10.0), clip(-30.0, 5.0, 20.0)),
+        (clip(0.0, 0.0, 150.0), clip(1.0, 1.0, 200.0)),
+        (clip(0.0, 0.0, 0.5), clip(0.1, 0.0, 0.9)),
+    ] {
+        assert_eq!(clip_line(a, b), None);
+    }
+}
+
+#[test]
+fn crossing_lines_are_trimmed_to_the_planes() {
+    let (a, b) = (clip(0.0, 0.0, 10.0), clip(100.0, 0.0, 10.0));
+    let (ca, cb) = clip_line(a, b).unwrap();
+
+    assert_eq!(ca, a);
+    assert!(inside(cb));
+    // Trimmed exactly at the right-hand plane x = w.
+    assert!((cb.x - cb.w).abs() < 1e-4);
+
+    // Beyond the far plane: trimmed at depth 1.
+    let (_, cb) = clip_line(clip(0.0, 0.0, 50.0), clip(0.0, 0.0, 500.0)).unwrap();
+    assert!((cb.z / cb.w - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn lines_behind_the_camera() {
+    // One end behind the eye: the visible part ends at the near plane.
+    let (a, b) = (clip(0.5, 0.5, 10.0), clip(0.5, 0.5, -10.0));
+    assert!(b.w < 0.0);
+    let (ca, cb) = clip_line(a, b).unwrap();
+    assert_eq!(ca, a);
+    assert!(inside(cb));
+    assert!((cb.w - perspective().near).abs() < 1e-4);
+
+    // Both ends behind the eye. Dividing by w would put them on screen.
+    let (a, b) = (clip(1.0, 0.0, -5.0), clip(-1.0, 0.5, -8.0));
+    assert!((a.x / a.w).abs() < 1.0 && (b.x / b.w).abs() < 1.0);
+    assert_eq!(clip_line(a, b), None);
+
+    // Passing behind the eye from one side to the other, never in view.
+    let (a, b) = (clip(-10.0, 0.0, 2.0), clip(10.0, 0.0, -2.0));
+    assert_eq!(clip_line(a, b), None);
+}
+
+#[test]
+fn legacy_lines_through_the_eye_stay_finite() {
+    // The legacy projection puts view depth into both z and w.
+    let (a, b) = (
+        Vec4::new(0.0, 0.0, 4.0, 4.0),
+        Vec4::new(0.0, 0.0, -4.0, -4.0),
+    );
+    let (_, cb) = clip_line(a, b).unwrap();
+    assert!(cb.w > 0.0);
+    assert!((cb.x / cb.w).is_finite() && (cb.y / cb.w).is_finite());
+}
+
+#[test]
+fn clip_scene_drops_invisible_lines() {
+    let scene = Scene {
+        num_lines: 3,
+        lines: vec![
+            Line::new(clip(0.0, 0.0, 5.0), clip(1.0, 1.0, 6.0)),
+            Line::new(clip(0.0, 0.0, -5.0), clip(1.0, 1.0, -6.0)),
+            Line::new(clip(0.0, 0.0, 5.0), clip(0.0, 0.0, -5.0)),
+        ],
+    };
+
+    let clipped = clip_scene(&scene);
+    assert_eq!(clipped.num_lines, 2);
+    assert!(clipped
+        .lines
+        .iter()
+        .all(|line| inside(*line.a) && inside(*line.b)));
+}
+
+#[test]
+fn camera_inside_a_cube_draws_only_visible_edges() {
+    let origin = Point::from_f32(-20.0, -20.0, -20.0);
+    let cube = Cube::new(40.0, &origin);
+
+    for projection in [Projection::Legacy, Projection::Perspective(perspective())] {
+        let params = ScreenParameter::new([1.0, 2.0, 5.0], 60.0, 30.0, 400.0, 400.0, 0.0, 0.0)
+            .with_projection(projection);
+        let lines = cube.get_lines();
+        let screen = create_screen_scene(
+            Scene {
+                num_lines: lines.len(),
+                lines,
+            },
+            &params,
+        );
+
+        assert!(screen.num_lines > 0 && screen.num_lines < 12);
+        for line in &screen.lines {
+            for v in [*line.a, *line.b] {
+                assert!(v.w > 0.0);
+                assert!(v.position.x.abs() <= 400.01 && v.position.y.abs() <= 400.01);
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::matrix::compare::{ElementDifference, Tolerance};
+use crate::matrix::fixed::Matrix4;
+use crate::matrix::scalar::Real;
+use crate::matrix::{GenericMatrix, Matrix, MatrixError};
+use crate::transformations::{rotate, RotationAxis};
+
+#[test]
+fn tolerances() {
+    let one_ulp_up = f32::from_bits(1.0f32.to_bits() + 1);
+
+    assert!(Tolerance::Ulps(1).accepts(1.0, one_ulp_up));
+    assert!(!Tolerance::Ulps(0).accepts(1.0, one_ulp_up));
+    assert!(Tolerance::Ulps(0).accepts(0.0, -0.0));
+    assert_eq!(f32::MIN_POSITIVE.ulps_between(-f32::MIN_POSITIVE), 2 << 23);
+    assert!(!Tolerance::Ulps(u64::MAX - 1).accepts(f32::NAN, f32::NAN));
+
+    assert!(Tolerance::Absolute(0.01).accepts(1e-9, -1e-9));
+    assert!(!Tolerance::Absolute(0.01).accepts(100.0, 100.1));
+    assert!(Tolerance::Relative(0.01).accepts(100.0, 100.9));
+    assert!(!Tolerance::Relative(0.01).accepts(1e-9, -1e-9));
+}
+
+#[test]
+fn products_in_different_orders_are_approximately_equal() {
+    let mut rng = StdRng::seed_from_u64(11);
+
+    for _ in 0..100 {
+        let (a, b, c) = (
+            rng.gen::<f32>() * 100.0,
+            rng.gen::<f32>() * 100.0,
+            rng.gen::<f32>() * 100.0,
+        );
+        let mat_a = Matrix::from_array([[a, b, c], [b, c, a], [c, a, b]]);
+        let mat_b = Matrix::from_array([[c, b, a], [a, c, b], [b, a, c]]);
+
+        let forward = mat_a.matrix_multiply(&mat_b).unwrap();
+        let backward = mat_a.map(|x| x as f64) * mat_b.map(|x| x as f64);
+        let backward = backward.map(|x| x as f32);
+
+        assert!(
+            forward.approx_eq(&backward, Tolerance::Relative(4.0 * f32::EPSILON)),
+            "{}",
+            forward.max_difference(&backward).unwrap()
+        );
+    }
+
+    assert!(!Matrix::new(2, 2).approx_eq(&Matrix::new(2, 3), Tolerance::Absolute(1.0)));
+}
+
+#[test]
+fn max_difference_names_the_worst_element() {
+    let a = Matrix::from_array([[1.0, 2.0], [3.0, 4.0]]);
+    let b = Matrix::from_array([[1.0, 2.5], [3.0, 3.0]]);
+
+    let worst = a.max_difference(&b).unwrap();
+    assert_eq!(
+        worst,
+        ElementDifference {
+            row: 1,
+            col: 1,
+            left: 4.0,
+            right: 3.0,
+            difference: 1.0,
+            ulps: 4.0f32.ulps_between(3.0),
+        }
+    );
+    assert!(worst
+        .to_string()
+        .starts_with("largest difference at (1, 1): 4 vs 3"));
+
+    let mut nan = b.clone();
+    nan[(0, 0)] = f32::NAN;
+    let worst = a.max_difference(&nan).unwrap();
+    assert_eq!((worst.row, worst.col, worst.ulps), (0, 0, u64::MAX));
+
+    assert_eq!(a.max_difference(&a).unwrap().difference, 0.0);
+    assert!(a.max_difference(&Matrix::new(2, 3)).is_err());
+    assert_eq!(
+        Matrix::new(0, 0).max_difference(&Matrix::new(0, 0)),
+        Err(MatrixError::EmptyInput)
+    );
+}
+
+#[test]
+fn condition_numbers() {
+    let identity = GenericMatrix::<f64>::identity(4);
+    assert!((identity.condition_number().unwrap() - 1.0).abs() < 1e-12);
+
+    let diagonal = GenericMatrix::from_array([[1000.0, 0.0], [0.0, 0.5f64]]);
+    assert!((diagonal.condition_number().unwrap() - 2000.0).abs() < 1e-9);
+
+    let singular = GenericMatrix::from_array([[1.0, 2.0], [2.0, 4.0f64]]);
+    assert!(singular.condition_number().unwrap() > 1e15);
+
+    let hilbert = GenericMatrix::from_vec(
+        (0..6)
+            .map(|i| (0..6).map(|j| 1.0 / (i + j + 1) as f64).collect())
+            .collect(),
+    )
+    .unwrap();
+    let cond = hilbert.condition_number().unwrap();
+    assert!((cond / 1.495e7 - 1.0).abs() < 1e-3, "{}", cond);
+
+    assert!(Matrix::new(0, 0).condition_number().is_err());
+}
+
+#[test]
+fn orthogonality_of_rotations() {
+    let rotation =
+        rotate(RotationAxis::Z, 0.7, 0.0, 0.0, 0.0) * rotate(RotationAxis::Z, -2.1, 0.0, 0.0, 0.0);
+    assert!(rotation.orthogonality_error() < 1e-6);
+
+    // Rotating about a point adds a translation, which is not orthogonal.
+    let about_point = rotate(RotationAxis::Z, 0.7, 1.0, 2.0, 3.0);
+    assert!(about_point.orthogonality_error() > 1.0);
+
+    let mut drifted = rotation;
+    drifted[(0, 0)] *= 1.01;
+    assert!(drifted.orthogonality_error() > 1e-3);
+
+    assert!(Matrix4::identity().approx_eq(
+        &(rotation * rotation.transpose()),
+        Tolerance::Absolute(1e-6)
+    ));
+    assert_eq!(
+        Matrix::new(2, 3).orthogonality_error(),
+        Err(MatrixError::NotSquare { rows: 2, cols: 3 })
+    );
+}
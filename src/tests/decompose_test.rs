@@ -0,0 +1,124 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::matrix::compare::Tolerance;
+use crate::matrix::fixed::Matrix4;
+use crate::matrix::MatrixError;
+use crate::quaternion::{EulerOrder, Quaternion};
+use crate::tests::helpers::{close, same_rotation};
+use crate::transformations::builder::Transform;
+use crate::transformations::{basic_scale, reflect, rotate, shear, translate, RotationAxis};
+use crate::vector::{Plane, Vec3};
+
+#[test]
+fn recovers_translation_rotation_and_scale() {
+    let mut rng = StdRng::seed_from_u64(22);
+
+    for _ in 0..20 {
+        let scale = Vec3::new(
+            rng.gen_range(0.2..5.0),
+            rng.gen_range(0.2..5.0),
+            rng.gen_range(0.2..5.0),
+        );
+        let rotation = Quaternion::from_euler(
+            EulerOrder::ZYX,
+            [
+                rng.gen_range(-3.0..3.0),
+                rng.gen_range(-1.5..1.5),
+                rng.gen_range(-3.0..3.0),
+            ],
+        );
+        let translation = Vec3::new(
+            rng.gen_range(-10.0..10.0),
+            rng.gen_range(-10.0..10.0),
+            rng.gen_range(-10.0..10.0),
+        );
+
+        let m = basic_scale(scale.x, scale.y, scale.z)
+            * rotation.to_matrix()
+            * translate(translation.x, translation.y, translation.z);
+        let parts = m.decompose_affine().unwrap();
+
+        assert!(close(parts.translation, translation));
+        assert!(close(parts.scale, scale));
+        assert!(same_rotation(parts.rotation, rotation));
+        assert!(parts.shear.iter().all(|s| s.abs() < 1e-4));
+        assert!(!parts.reflection);
+        assert!(parts.to_matrix().approx_eq(&m, Tolerance::Absolute(1e-4)));
+    }
+}
+
+#[test]
+fn recovers_shear() {
+    let m = basic_scale(2.0, 3.0, 0.5)
+        * shear(0.4, -0.2, 0.0, 0.7, 0.0, 0.0)
+        * rotate(RotationAxis::X, 0.6, 0.0, 0.0, 0.0)
+        * translate(1.0, 2.0, 3.0);
+    let parts = m.decompose_affine().unwrap();
+
+    assert!(close(parts.scale, Vec3::new(2.0, 3.0, 0.5)));
+    for (found, expected) in parts.shear.iter().zip([0.4, -0.2, 0.7]) {
+        assert!((found - expected).abs() < 1e-4);
+    }
+    assert!(parts.to_matrix().approx_eq(&m, Tolerance::Absolute(1e-4)));
+}
+
+#[test]
+fn reports_reflection_with_a_proper_rotation() {
+    let mirror = reflect(&Plane::new(1.0, 1.0, 0.0, 0.0).unwrap());
+    let m = basic_scale(1.0, 2.0, 3.0) * mirror * translate(0.0, 0.0, 5.0);
+    let parts = m.decompose_affine().unwrap();
+
+    assert!(parts.reflection);
+    assert!(parts.scale.x < 0.0);
+    assert!((parts.rotation.norm() - 1.0).abs() < 1e-5);
+    assert!(parts.to_matrix().approx_eq(&m, Tolerance::Absolute(1e-4)));
+
+    let parts = translate(1.0, 1.0, 1.0).decompose_affine().unwrap();
+    assert!(!parts.reflection);
+}
+
+#[test]
+fn euler_angles_of_a_chain() {
+    let m = Transform::new()
+        .rotate(RotationAxis::X, 0.3)
+        .rotate(RotationAxis::Z, -0.5)
+        .translate(4.0, 0.0, 0.0)
+        .build();
+    let parts = m.decompose_affine().unwrap();
+
+    let [a, b, c] = parts.euler(EulerOrder::XYZ);
+    assert!((a - 0.3).abs() < 1e-5);
+    assert!(b.abs() < 1e-5);
+    assert!((c + 0.5).abs() < 1e-5);
+    assert!(close(parts.translation, Vec3::new(4.0, 0.0, 0.0)));
+}
+
+#[test]
+fn divides_out_homogeneous_scale() {
+    let m = translate(1.0, 2.0, 3.0) * 2.0;
+    let parts = m.decompose_affine().unwrap();
+
+    assert!(close(parts.translation, Vec3::new(1.0, 2.0, 3.0)));
+    assert!(close(parts.scale, Vec3::new(1.0, 1.0, 1.0)));
+}
+
+#[test]
+fn rejects_perspective_and_degenerate_matrices() {
+    let mut perspective = Matrix4::identity();
+    perspective[(2, 3)] = 1.0;
+    assert_eq!(perspective.decompose_affine(), Err(MatrixError::NotAffine));
+    assert_eq!(
+        Matrix4::new().decompose_affine(),
+        Err(MatrixError::NotAffine)
+    );
+
+    for m in [
+        basic_scale(0.0, 1.0, 1.0),
+        basic_scale(1.0, 0.0, 1.0),
+        basic_scale(1.0, 1.0, 0.0),
+        shear(1.0, 0.0, 1.0, 0.0, 0.0, 0.0) * basic_scale(1.0, 1.0, 1.0),
+    ] {
+        assert_eq!(m.decompose_affine(), Err(MatrixError::Singular));
+    }
+}
@@ -0,0 +1,153 @@
+use crate::matrix::{Matrix, MatrixError};
+use crate::tests::helpers::random_matrix;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn random_spd(rng: &mut StdRng, size: usize) -> Matrix {
+    let a = random_matrix(rng, size, size);
+
+    let mut spd = a.transpose().matrix_multiply(&a).unwrap();
+    for i in 0..size {
+        spd.set(i, i, spd.at(i, i).unwrap() + 1.0).unwrap();
+    }
+    return spd;
+}
+
+fn max_residual(a: &Matrix, x: &Matrix, b: &Matrix) -> f32 {
+    let ax = a.matrix_multiply(x).unwrap();
+    let mut max = 0.0_f32;
+    for i in 0..b.num_rows() {
+        for j in 0..b.num_cols() {
+            max = max.max((ax.at(i, j).unwrap() - b.at(i, j).unwrap()).abs());
+        }
+    }
+    return max;
+}
+
+fn max_difference(a: &Matrix, b: &Matrix) -> f32 {
+    return max_residual(a, &Matrix::identity(a.num_cols()), b);
+}
+
+#[test]
+fn lu_reconstructs_and_solves() {
+    let mut rng = StdRng::seed_from_u64(2);
+
+    for size in 1..8 {
+        let mut a = random_matrix(&mut rng, size, size);
+        for i in 0..size {
+            a.set(i, i, a.at(i, i).unwrap() + size as f32).unwrap();
+        }
+        let b = random_matrix(&mut rng, size, 3);
+
+        let lu = a.lu().unwrap();
+        let pa = lu.p().matrix_multiply(&a).unwrap();
+        let l_u = lu.l().matrix_multiply(&lu.u()).unwrap();
+        assert!(max_difference(&pa, &l_u) < 1e-5);
+        assert!((lu.determinant() - a.determinant().unwrap()).abs() < 1e-3);
+
+        let x = a.solve(&b).unwrap();
+        assert!(max_residual(&a, &x, &b) < 1e-5);
+    }
+}
+
+#[test]
+fn lu_solve_errors() {
+    let singular = Matrix::from_vec(vec![vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap();
+    let b = Matrix::from_vec(vec![vec![1.0], vec![1.0]]).unwrap();
+    assert_eq!(singular.solve(&b).unwrap_err(), MatrixError::Singular);
+
+    let a = Matrix::identity(3);
+    assert_eq!(
+        a.solve(&b).unwrap_err(),
+        MatrixError::DimensionMismatch {
+            left: (3, 3),
+            right: (2, 1)
+        }
+    );
+}
+
+#[test]
+fn qr_is_orthogonal_and_triangular() {
+    let a = random_matrix(&mut StdRng::seed_from_u64(3), 6, 4);
+    let qr = a.qr();
+
+    let qtq = qr.q().transpose().matrix_multiply(qr.q()).unwrap();
+    assert!(max_difference(&qtq, &Matrix::identity(6)) < 1e-5);
+
+    for i in 0..6 {
+        for j in 0..i.min(4) {
+            assert_eq!(qr.r().at(i, j).unwrap(), 0.0);
+        }
+    }
+
+    let q_r = qr.q().matrix_multiply(qr.r()).unwrap();
+    assert!(max_difference(&q_r, &a) < 1e-5);
+}
+
+#[test]
+fn least_squares_fits_plane() {
+    // Points on z = 2x - 3y + 5, one row of [x, y, 1] per point.
+    let mut rng = StdRng::seed_from_u64(4);
+    let mut a = Matrix::new(20, 3);
+    let mut b = Matrix::new(20, 1);
+    for i in 0..20 {
+        let (x, y): (f32, f32) = (rng.gen_range(-10.0..10.0), rng.gen_range(-10.0..10.0));
+        a.set(i, 0, x).unwrap();
+        a.set(i, 1, y).unwrap();
+        a.set(i, 2, 1.0).unwrap();
+        b.set(i, 0, 2.0 * x - 3.0 * y + 5.0).unwrap();
+    }
+
+    let coefficients = a.solve_least_squares(&b).unwrap();
+    assert!((coefficients.at(0, 0).unwrap() - 2.0).abs() < 1e-4);
+    assert!((coefficients.at(1, 0).unwrap() + 3.0).abs() < 1e-4);
+    assert!((coefficients.at(2, 0).unwrap() - 5.0).abs() < 1e-3);
+    assert!(max_residual(&a, &coefficients, &b) < 1e-3);
+}
+
+#[test]
+fn least_squares_residual_is_orthogonal() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let a = random_matrix(&mut rng, 10, 3);
+    let b = random_matrix(&mut rng, 10, 1);
+    let x = a.solve_least_squares(&b).unwrap();
+    let ax = a.matrix_multiply(&x).unwrap();
+
+    // At the minimum, A^T * (A * x - b) vanishes.
+    for j in 0..3 {
+        let dot: f32 = (0..10)
+            .map(|i| a.at(i, j).unwrap() * (ax.at(i, 0).unwrap() - b.at(i, 0).unwrap()))
+            .sum();
+        assert!(dot.abs() < 1e-4);
+    }
+
+    let wide = random_matrix(&mut rng, 2, 3);
+    assert_eq!(
+        wide.solve_least_squares(&Matrix::new(2, 1)).unwrap_err(),
+        MatrixError::Underdetermined { rows: 2, cols: 3 }
+    );
+}
+
+#[test]
+fn cholesky_reconstructs_and_solves() {
+    let mut rng = StdRng::seed_from_u64(6);
+
+    for size in 1..7 {
+        let a = random_spd(&mut rng, size);
+        let b = random_matrix(&mut rng, size, 2);
+        let cholesky = a.cholesky().unwrap();
+
+        let l = cholesky.l();
+        let llt = l.matrix_multiply(&l.transpose()).unwrap();
+        assert!(max_difference(&llt, &a) < 1e-4);
+
+        let x = cholesky.solve(&b).unwrap();
+        assert!(max_residual(&a, &x, &b) < 1e-4);
+    }
+
+    let indefinite = Matrix::from_vec(vec![vec![1.0, 2.0], vec![2.0, 1.0]]).unwrap();
+    assert_eq!(
+        indefinite.cholesky().unwrap_err(),
+        MatrixError::NotPositiveDefinite
+    );
+}
@@ -0,0 +1,111 @@
+use crate::matrix::{Matrix, MatrixError};
+use crate::tests::helpers::{assert_near, random_matrix};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn assert_orthonormal_columns(m: &Matrix) {
+    let mtm = m.transpose().matrix_multiply(m).unwrap();
+    assert_near(&mtm, &Matrix::identity(m.num_cols()), 1e-4);
+}
+
+fn assert_descending(values: &[f32]) {
+    values
+        .windows(2)
+        .for_each(|pair| assert!(pair[0] >= pair[1], "{:?}", values));
+}
+
+#[test]
+fn eigen_of_covariance_matrix() {
+    // Covariance of a random point cloud is symmetric positive semi-definite.
+    let points = random_matrix(&mut StdRng::seed_from_u64(3), 50, 3);
+    let covariance = points.transpose().matrix_multiply(&points).unwrap();
+
+    let eigen = covariance.symmetric_eigen().unwrap();
+    assert_descending(eigen.values());
+    assert_orthonormal_columns(eigen.vectors());
+
+    let v = eigen.vectors();
+    for (i, value) in eigen.values().iter().enumerate() {
+        for k in 0..3 {
+            let av: f32 = (0..3)
+                .map(|j| covariance.at(k, j).unwrap() * v.at(j, i).unwrap())
+                .sum();
+            assert!((av - value * v.at(k, i).unwrap()).abs() < 1e-3);
+        }
+    }
+}
+
+#[test]
+fn eigen_of_diagonal_and_known_matrices() {
+    let diagonal = Matrix::from_vec(vec![
+        vec![1.0, 0.0, 0.0],
+        vec![0.0, 5.0, 0.0],
+        vec![0.0, 0.0, 3.0],
+    ])
+    .unwrap();
+    assert_eq!(
+        diagonal.symmetric_eigen().unwrap().values(),
+        &[5.0, 3.0, 1.0]
+    );
+
+    let mat = Matrix::from_vec(vec![vec![2.0, 1.0], vec![1.0, 2.0]]).unwrap();
+    let eigen = mat.symmetric_eigen().unwrap();
+    assert!((eigen.values()[0] - 3.0).abs() < 1e-5);
+    assert!((eigen.values()[1] - 1.0).abs() < 1e-5);
+
+    let asymmetric = Matrix::from_vec(vec![vec![1.0, 2.0], vec![3.0, 4.0]]).unwrap();
+    assert_eq!(
+        asymmetric.symmetric_eigen().unwrap_err(),
+        MatrixError::NotSymmetric
+    );
+}
+
+#[test]
+fn svd_reconstructs_input() {
+    let mut rng = StdRng::seed_from_u64(103);
+
+    for (rows, cols) in [(4, 4), (6, 3), (3, 6), (1, 5), (5, 1)] {
+        let a = random_matrix(&mut rng, rows, cols);
+        let svd = a.svd().unwrap();
+
+        assert_eq!(svd.singular_values().len(), rows.min(cols));
+        assert_descending(svd.singular_values());
+        assert_orthonormal_columns(svd.u());
+        assert_orthonormal_columns(svd.v());
+
+        let mut us = svd.u().clone();
+        for (j, sigma) in svd.singular_values().iter().enumerate() {
+            for i in 0..rows {
+                us.set(i, j, us.at(i, j).unwrap() * sigma).unwrap();
+            }
+        }
+        let usvt = us.matrix_multiply(&svd.v().transpose()).unwrap();
+        assert_near(&usvt, &a, 1e-4);
+    }
+}
+
+#[test]
+fn svd_of_rank_deficient_matrix() {
+    let a = Matrix::from_vec(vec![
+        vec![1.0, 2.0, 3.0],
+        vec![2.0, 4.0, 6.0],
+        vec![1.0, 1.0, 1.0],
+    ])
+    .unwrap();
+    let svd = a.svd().unwrap();
+
+    assert!(svd.singular_values()[1] > 1e-3);
+    assert!(svd.singular_values()[2].abs() < 1e-4);
+    assert_orthonormal_columns(svd.u());
+    assert_orthonormal_columns(svd.v());
+}
+
+#[test]
+fn unconverged_sweeps_are_errors() {
+    // NaN never satisfies the convergence test, so every sweep runs.
+    let nan = Matrix::from_vec(vec![vec![1.0, f32::NAN], vec![f32::NAN, 1.0]]).unwrap();
+    let expected = MatrixError::NotConverged { iterations: 64 };
+
+    assert_eq!(nan.symmetric_eigen().unwrap_err(), expected);
+    assert_eq!(nan.svd().unwrap_err(), expected);
+}
@@ -0,0 +1,99 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::matrix::fixed::{Matrix4, SMatrix};
+use crate::matrix::{Matrix, MatrixError};
+use crate::solid::Point;
+use crate::transformations::{scale, translate};
+
+#[test]
+fn fixed_multiply_matches_dynamic() {
+    let a = SMatrix::from_array([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let b = SMatrix::from_array([[7.0, 8.0], [9.0, 10.0], [11.0, 12.0]]);
+
+    let product: SMatrix<2, 2> = a * b;
+    let expected = a.to_matrix() * b.to_matrix();
+    assert_eq!(product.to_matrix(), expected);
+}
+
+#[test]
+fn conversions_round_trip() {
+    let dynamic = Matrix::from_array([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+    let fixed = SMatrix::<3, 2>::try_from(&dynamic).unwrap();
+    assert_eq!(fixed[(2, 1)], 6.0);
+    assert_eq!(Matrix::from(fixed), dynamic);
+
+    assert_eq!(
+        Matrix4::try_from(dynamic).unwrap_err(),
+        MatrixError::DimensionMismatch {
+            left: (3, 2),
+            right: (4, 4)
+        }
+    );
+}
+
+#[test]
+fn identity_and_transpose() {
+    let m = SMatrix::from_array([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    assert_eq!(
+        m.transpose(),
+        SMatrix::from_array([[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]])
+    );
+    assert_eq!(
+        Matrix4::identity() * translate(1.0, 2.0, 3.0),
+        translate(1.0, 2.0, 3.0)
+    );
+    assert_eq!(Matrix4::identity().to_matrix(), Matrix::identity(4));
+}
+
+#[test]
+fn transformations_apply_to_points() {
+    let p = Point::from_f32(1.0, 2.0, 3.0);
+
+    let moved = p.apply_matrix(&translate(10.0, 20.0, 30.0));
+    assert_eq!((moved.x, moved.y, moved.z), (11.0, 22.0, 33.0));
+
+    // Scaling about (1, 1, 1) leaves that point fixed.
+    let scaled = p.apply_matrix(&scale(2.0, 2.0, 2.0, 1.0, 1.0, 1.0));
+    assert_eq!((scaled.x, scaled.y, scaled.z), (1.0, 3.0, 5.0));
+}
+
+#[test]
+fn batch_transform_matches_single_points() {
+    let mut rng = StdRng::seed_from_u64(109);
+    let matrix = scale(1.5, 0.5, 2.0, 3.0, -1.0, 0.25) * translate(-4.0, 7.5, 0.125);
+
+    // The larger batch takes the multi-threaded path.
+    for count in [0, 1, 7, 5000] {
+        let points = (0..count)
+            .map(|_| Point::from_f32(rng.gen(), rng.gen(), rng.gen()))
+            .collect::<Vec<Point>>();
+        let refs = points.iter().collect::<Vec<&Point>>();
+
+        let batch = Point::apply_matrix_batch(&refs, &matrix);
+        assert_eq!(batch.len(), count);
+        for (p, q) in points.iter().zip(&batch) {
+            let single = p.apply_matrix(&matrix);
+            assert_eq!((q.x, q.y, q.z), (single.x, single.y, single.z));
+        }
+    }
+
+    // Every product in x is -0.0; summing them without a zero start would
+    // give -0.0 instead of the +0.0 the matrix multiply produces.
+    let flip = Matrix4::from_array([
+        [-1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [-0.0, 0.0, 0.0, 1.0],
+    ]);
+    let points = [
+        Point::from_f32(0.0, -1.0, -1.0),
+        Point::from_f32(-0.0, 2.0, 0.0),
+    ];
+    let refs = points.iter().collect::<Vec<&Point>>();
+    let bits = |p: &Point| (p.x.to_bits(), p.y.to_bits(), p.z.to_bits());
+
+    for (p, q) in points.iter().zip(&Point::apply_matrix_batch(&refs, &flip)) {
+        assert_eq!(bits(q), bits(&p.apply_matrix(&flip)));
+    }
+}
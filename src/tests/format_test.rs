@@ -0,0 +1,97 @@
+use crate::matrix::fixed::Matrix4;
+use crate::matrix::format::{Alignment, Layout, MatrixFormat, Precision};
+use crate::matrix::scalar::Rational;
+use crate::matrix::{GenericMatrix, Matrix};
+
+fn sample() -> Matrix {
+    return Matrix::from_array([[1.0, -0.5, 100.25], [1.0 / 3.0, -0.00001, 2.0]]);
+}
+
+#[test]
+fn display_pads_columns_independently() {
+    let m = Matrix::from_array([[1.0, 200.0], [-30.0, 4.0]]);
+    assert_eq!(m.to_string(), "\n[   1 200 ]\n[ -30   4 ]\n");
+    assert_eq!(format!("{:.1}", m), "\n[   1.0 200.0 ]\n[ -30.0   4.0 ]\n");
+    assert_eq!(
+        format!("{:.2}", Matrix4::identity()).lines().nth(1),
+        Some("[ 1.00 0.00 0.00 0.00 ]")
+    );
+}
+
+#[test]
+fn display_of_empty_matrices() {
+    assert_eq!(Matrix::new(0, 0).to_string(), "\n[ ]\n");
+    assert_eq!(Matrix::new(0, 3).to_string(), "\n[ ]\n");
+    assert_eq!(Matrix::new(2, 0).to_string(), "\n[ ]\n[ ]\n");
+
+    let compact = MatrixFormat::new().layout(Layout::Compact);
+    assert_eq!(Matrix::new(0, 0).format_with(&compact), "[]");
+    let latex = MatrixFormat::new().layout(Layout::Latex);
+    assert_eq!(
+        Matrix::new(0, 0).format_with(&latex),
+        "\\begin{bmatrix}\n\\end{bmatrix}"
+    );
+}
+
+#[test]
+fn precision_modes() {
+    let fixed = MatrixFormat::new()
+        .precision(Precision::Fixed(2))
+        .layout(Layout::Compact);
+    assert_eq!(
+        sample().format_with(&fixed),
+        "[[1.00, -0.50, 100.25], [0.33, 0.00, 2.00]]"
+    );
+
+    let significant = fixed.precision(Precision::Significant(3));
+    assert_eq!(
+        sample().format_with(&significant),
+        "[[1.00, -0.500, 100], [0.333, -1.00e-5, 2.00]]"
+    );
+
+    let values = GenericMatrix::from_array([[123456.0, 0.000123456, 9.999, f64::INFINITY]]);
+    assert_eq!(
+        values.format_with(&significant),
+        "[[1.23e5, 0.000123, 10.0, inf]]"
+    );
+
+    // Exact types keep their own notation at full precision.
+    let rational = GenericMatrix::from_array([[Rational::new(1, 3), Rational::from_integer(2)]]);
+    let compact = MatrixFormat::new().layout(Layout::Compact);
+    assert_eq!(rational.format_with(&compact), "[[1/3, 2]]");
+    assert_eq!(
+        rational.format_with(&compact.precision(Precision::Fixed(3))),
+        "[[0.333, 2.000]]"
+    );
+}
+
+#[test]
+fn latex_layout() {
+    let format = MatrixFormat::new()
+        .precision(Precision::Fixed(1))
+        .layout(Layout::Latex);
+    assert_eq!(
+        sample().format_with(&format),
+        "\\begin{bmatrix}\n    1.0 & -0.5 & 100.2 \\\\\n    0.3 &  0.0 &   2.0\n\\end{bmatrix}"
+    );
+}
+
+#[test]
+fn markdown_layout_and_alignment() {
+    let m = Matrix::from_array([[1.0, 22.0], [333.0, 4.0]]);
+
+    let right = MatrixFormat::new().layout(Layout::Markdown);
+    assert_eq!(
+        m.format_with(&right),
+        "|   0 |   1 |\n| --: | --: |\n|   1 |  22 |\n| 333 |   4 |\n"
+    );
+
+    let left = right.alignment(Alignment::Left);
+    assert_eq!(
+        m.format_with(&left),
+        "| 0   | 1   |\n| :-- | :-- |\n| 1   | 22  |\n| 333 | 4   |\n"
+    );
+
+    let center = MatrixFormat::new().alignment(Alignment::Center);
+    assert_eq!(m.format_with(&center), "\n[  1  22 ]\n[ 333 4  ]\n");
+}
@@ -0,0 +1,163 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::matrix::compare::Tolerance;
+use crate::matrix::fixed::Matrix4;
+use crate::matrix::scalar::Rational;
+use crate::matrix::{GenericMatrix, MatrixError};
+use crate::solid::Point;
+use crate::transformations::{interpolate, rotate, translate, RotationAxis};
+
+type Matrix64 = GenericMatrix<f64>;
+
+fn assert_close(a: &Matrix64, b: &Matrix64, tolerance: f64) {
+    assert!(
+        a.approx_eq(b, Tolerance::Absolute(tolerance)),
+        "{}",
+        a.max_difference(b).unwrap()
+    );
+}
+
+fn rotation_2d(theta: f64) -> Matrix64 {
+    return GenericMatrix::from_array([[theta.cos(), theta.sin()], [-theta.sin(), theta.cos()]]);
+}
+
+#[test]
+fn power_by_squaring() {
+    let m = GenericMatrix::from_array([[1, 1], [1, 0]]).map(|x: i64| x);
+    // Powers of the Fibonacci matrix hold consecutive Fibonacci numbers.
+    assert_eq!(
+        m.pow(40).unwrap(),
+        GenericMatrix::from_array([[165580141, 102334155], [102334155, 63245986]])
+    );
+    assert_eq!(m.pow(0).unwrap(), GenericMatrix::identity(2));
+    assert_eq!(
+        m.pow(7).unwrap(),
+        (0..7).fold(GenericMatrix::identity(2), |p, _| &p * &m)
+    );
+
+    let r = GenericMatrix::from_array([[2, 1], [7, 4]]).map(Rational::from_integer);
+    assert_eq!(
+        &r.powi(-3).unwrap() * &r.pow(3).unwrap(),
+        GenericMatrix::identity(2)
+    );
+    assert_eq!(r.powi(-1).unwrap(), r.inverse().unwrap());
+
+    assert!(GenericMatrix::<i64>::new(2, 3).pow(2).is_err());
+    assert!(GenericMatrix::<f64>::new(2, 2).powi(-1).is_err());
+}
+
+#[test]
+fn exponential_of_known_matrices() {
+    assert_eq!(Matrix64::new(3, 3).exp().unwrap(), Matrix64::identity(3));
+
+    let diagonal = GenericMatrix::from_array([[1.0, 0.0], [0.0, -2.0]]);
+    let expected = GenericMatrix::from_array([[1f64.exp(), 0.0], [0.0, (-2f64).exp()]]);
+    assert_close(&diagonal.exp().unwrap(), &expected, 1e-14);
+
+    // The generator of a rotation exponentiates to the rotation, even for
+    // angles that need several squarings.
+    for theta in [0.1, 1.0, 3.0, 10.0] {
+        let generator = GenericMatrix::from_array([[0.0, theta], [-theta, 0.0]]);
+        assert_close(&generator.exp().unwrap(), &rotation_2d(theta), 1e-12);
+    }
+
+    // Nilpotent: the series stops after the linear term.
+    let shear = GenericMatrix::from_array([[0.0, 3.0], [0.0, 0.0]]);
+    assert_close(
+        &shear.exp().unwrap(),
+        &GenericMatrix::from_array([[1.0, 3.0], [0.0, 1.0]]),
+        1e-14,
+    );
+
+    assert!(Matrix64::new(2, 3).exp().is_err());
+    let infinite = GenericMatrix::from_array([[f64::INFINITY, 0.0], [0.0, 1.0]]);
+    assert_eq!(infinite.exp(), Err(MatrixError::NonFinite));
+}
+
+#[test]
+fn logarithm_round_trips() {
+    let mut rng = StdRng::seed_from_u64(15);
+
+    for _ in 0..20 {
+        let a = Matrix64::new(4, 4).map(|_| rng.gen_range(-0.5..0.5));
+        assert_close(&a.exp().unwrap().log().unwrap(), &a, 1e-10);
+
+        // Small enough that every eigenvalue of `I + a` stays in the right
+        // half-plane, so the principal logarithm exists.
+        let b = &Matrix64::identity(4) + &(&a * 0.4);
+        assert_close(&b.log().unwrap().exp().unwrap(), &b, 1e-10);
+    }
+
+    assert_close(
+        &rotation_2d(2.5).log().unwrap().exp().unwrap(),
+        &rotation_2d(2.5),
+        1e-10,
+    );
+    assert_close(
+        &rotation_2d(2.5).log().unwrap(),
+        &GenericMatrix::from_array([[0.0, 2.5], [-2.5, 0.0]]),
+        1e-10,
+    );
+
+    let positive = GenericMatrix::from_array([[4.0, 1.0], [1.0, 3.0]]);
+    assert_close(&positive.log().unwrap().exp().unwrap(), &positive, 1e-10);
+}
+
+#[test]
+fn logarithm_errors() {
+    // Exactly a half turn; rotation_2d(PI) is off by rounding and has a log.
+    let half_turn = GenericMatrix::from_array([[-1.0, 0.0], [0.0, -1.0]]);
+    assert_eq!(half_turn.log(), Err(MatrixError::NoPrincipalLogarithm));
+
+    let negative = GenericMatrix::from_array([[-1.0, 0.0], [0.0, 2.0]]);
+    assert_eq!(negative.log(), Err(MatrixError::NoPrincipalLogarithm));
+
+    assert_eq!(
+        Matrix64::new(2, 2).log(),
+        Err(MatrixError::NoPrincipalLogarithm)
+    );
+    assert!(Matrix64::new(1, 2).log().is_err());
+    let nan = GenericMatrix::from_array([[1.0, f64::NAN], [0.0, 1.0]]);
+    assert_eq!(nan.log(), Err(MatrixError::NonFinite));
+}
+
+#[test]
+fn interpolation_between_transforms() {
+    let from = translate(1.0, 2.0, 3.0);
+    let to = rotate(RotationAxis::Z, 1.2, 0.0, 0.0, 0.0) * translate(-4.0, 0.5, 7.0);
+    let close = Tolerance::Absolute(1e-5);
+
+    assert_eq!(interpolate(&from, &to, 0.0).unwrap(), from);
+    assert!(interpolate(&from, &to, 1.0).unwrap().approx_eq(&to, close));
+
+    // Halfway along a pure rotation is half the angle.
+    let quarter = rotate(RotationAxis::Z, std::f32::consts::FRAC_PI_2, 0.0, 0.0, 0.0);
+    let eighth = rotate(RotationAxis::Z, std::f32::consts::FRAC_PI_4, 0.0, 0.0, 0.0);
+    let halfway = interpolate(&Matrix4::identity(), &quarter, 0.5).unwrap();
+    assert!(halfway.approx_eq(&eighth, close));
+
+    // Every intermediate transform stays rigid.
+    for k in 0..=10 {
+        let blend = interpolate(&from, &to, k as f32 / 10.0).unwrap();
+        let a = Point::from_f32(1.0, 0.0, 0.0).apply_matrix(&blend);
+        let b = Point::from_f32(0.0, 1.0, 0.0).apply_matrix(&blend);
+        let distance = ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt();
+        assert!((distance - 2f32.sqrt()).abs() < 1e-5);
+    }
+
+    let half_turn = Matrix4::from_array([
+        [-1.0, 0.0, 0.0, 0.0],
+        [0.0, -1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+    assert_eq!(
+        interpolate(&Matrix4::identity(), &half_turn, 0.5),
+        Err(MatrixError::NoPrincipalLogarithm)
+    );
+    assert_eq!(
+        interpolate(&Matrix4::new(), &to, 0.5),
+        Err(MatrixError::Singular)
+    );
+}
@@ -0,0 +1,242 @@
+use std::io::Cursor;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::matrix::io::{MarketFormat, ReadError};
+use crate::matrix::sparse::CsrMatrix;
+use crate::matrix::{GenericMatrix, Matrix};
+use crate::tests::helpers::random_matrix;
+use crate::transformations::{rotate, RotationAxis};
+
+fn parse_position(result: Result<Matrix, ReadError>) -> (usize, usize) {
+    match result {
+        Err(ReadError::Parse { line, column, .. }) => return (line, column),
+        other => panic!("expected a parse error, found {:?}", other),
+    }
+}
+
+#[test]
+fn csv_round_trip() {
+    let m = random_matrix(&mut StdRng::seed_from_u64(12), 5, 3) * 1e6;
+
+    let mut buffer = Vec::new();
+    m.write_csv(&mut buffer).unwrap();
+    assert_eq!(Matrix::read_csv(Cursor::new(buffer)).unwrap(), m);
+
+    let text = "1, 2.5 ,-3\n\n4,5,6e2\n";
+    assert_eq!(
+        Matrix::read_csv(Cursor::new(text)).unwrap(),
+        Matrix::from_array([[1.0, 2.5, -3.0], [4.0, 5.0, 600.0]])
+    );
+}
+
+#[test]
+fn csv_errors_report_line_and_column() {
+    assert_eq!(
+        parse_position(Matrix::read_csv(Cursor::new("1,2\n3, x\n"))),
+        (2, 4)
+    );
+    assert_eq!(
+        parse_position(Matrix::read_csv(Cursor::new("1,2\n3,4,5\n"))),
+        (2, 5)
+    );
+    assert_eq!(
+        parse_position(Matrix::read_csv(Cursor::new("1,2\n3\n"))),
+        (2, 2)
+    );
+    assert_eq!(parse_position(Matrix::read_csv(Cursor::new("\n"))), (2, 1));
+
+    let error = Matrix::read_csv(Cursor::new("1,2\n3,,4\n")).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "line 2, column 3: expected a number, found ``"
+    );
+}
+
+#[test]
+fn matrix_market_round_trip() {
+    let transform = rotate(RotationAxis::Z, 0.3, 1.0, 2.0, 3.0).to_matrix();
+
+    for format in [MarketFormat::Array, MarketFormat::Coordinate] {
+        let mut buffer = Vec::new();
+        transform.write_matrix_market(&mut buffer, format).unwrap();
+        assert_eq!(
+            Matrix::read_matrix_market(Cursor::new(buffer)).unwrap(),
+            transform
+        );
+    }
+
+    let mut buffer = Vec::new();
+    transform
+        .write_matrix_market(&mut buffer, MarketFormat::Array)
+        .unwrap();
+    let text = String::from_utf8(buffer).unwrap();
+    assert!(text.starts_with("%%MatrixMarket matrix array real general\n4 4\n"));
+
+    let sparse = CsrMatrix::from_dense(&transform);
+    let mut buffer = Vec::new();
+    sparse.write_matrix_market(&mut buffer).unwrap();
+    assert_eq!(
+        CsrMatrix::read_matrix_market(Cursor::new(buffer)).unwrap(),
+        sparse
+    );
+}
+
+#[test]
+fn matrix_market_storage_variants() {
+    let array = "%%MatrixMarket matrix array real general\n% a comment\n2 3\n1\n4\n2\n5\n3\n6\n";
+    assert_eq!(
+        Matrix::read_matrix_market(Cursor::new(array)).unwrap(),
+        Matrix::from_array([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+    );
+
+    let symmetric = "%%MatrixMarket matrix array real symmetric\n2 2\n1\n2\n3\n";
+    assert_eq!(
+        Matrix::read_matrix_market(Cursor::new(symmetric)).unwrap(),
+        Matrix::from_array([[1.0, 2.0], [2.0, 3.0]])
+    );
+
+    let skew = "%%MatrixMarket matrix coordinate real skew-symmetric\n3 3 1\n3 1 2.5\n";
+    assert_eq!(
+        Matrix::read_matrix_market(Cursor::new(skew)).unwrap(),
+        Matrix::from_array([[0.0, 0.0, -2.5], [0.0, 0.0, 0.0], [2.5, 0.0, 0.0]])
+    );
+
+    let pattern = "%%MatrixMarket matrix coordinate pattern symmetric\n2 2 2\n1 1\n2 1\n";
+    let csr = CsrMatrix::<f64>::read_matrix_market(Cursor::new(pattern)).unwrap();
+    assert_eq!(
+        csr.to_dense(),
+        GenericMatrix::from_array([[1.0, 1.0], [1.0, 0.0]])
+    );
+
+    let duplicates = "%%MatrixMarket matrix coordinate integer general\n1 2 2\n1 2 3\n1 2 4\n";
+    assert_eq!(
+        Matrix::read_matrix_market(Cursor::new(duplicates)).unwrap(),
+        Matrix::from_array([[0.0, 7.0]])
+    );
+}
+
+#[test]
+fn matrix_market_errors_report_line_and_column() {
+    let read =
+        |text: &str| parse_position(Matrix::read_matrix_market(Cursor::new(text.to_string())));
+
+    assert_eq!(read(""), (1, 1));
+    assert_eq!(
+        read("%%MatrixMarket matrix array complex general\n"),
+        (1, 29)
+    );
+    assert_eq!(
+        read("%%MatrixMarket matrix array real hermitian\n"),
+        (1, 34)
+    );
+    assert_eq!(
+        read("%%MatrixMarket matrix array real general\n% only\n"),
+        (3, 1)
+    );
+    assert_eq!(
+        read("%%MatrixMarket matrix array real general\n2 x\n"),
+        (2, 3)
+    );
+    assert_eq!(
+        read("%%MatrixMarket matrix array real general\n1 2\n1\n"),
+        (4, 1)
+    );
+    assert_eq!(
+        read("%%MatrixMarket matrix array real general\n1 1\n1\n2\n"),
+        (4, 1)
+    );
+    assert_eq!(
+        read("%%MatrixMarket matrix coordinate real general\n2 2 1\n1 3 1.0\n"),
+        (3, 3)
+    );
+    assert_eq!(
+        read("%%MatrixMarket matrix coordinate real general\n2 2 1\n1 2\n"),
+        (3, 4)
+    );
+    assert_eq!(
+        read("%%MatrixMarket matrix coordinate real symmetric\n2 2 1\n1 2 1.0\n"),
+        (3, 3)
+    );
+    assert_eq!(
+        read("%%MatrixMarket matrix coordinate real skew-symmetric\n2 2 1\n2 2 1.0\n"),
+        (3, 3)
+    );
+}
+
+#[test]
+fn matrix_market_rejects_huge_dense_sizes() {
+    let huge = "%%MatrixMarket matrix coordinate real general\n100000 100000 1\n1 1 2.0\n";
+    let error = Matrix::read_matrix_market(Cursor::new(huge)).unwrap_err();
+    assert_eq!(parse_position(Err(error)), (2, 1));
+
+    let csr = CsrMatrix::<f64>::read_matrix_market(Cursor::new(huge)).unwrap();
+    assert_eq!(
+        (csr.num_rows(), csr.num_cols(), csr.nnz()),
+        (100000, 100000, 1)
+    );
+
+    let overflow = format!(
+        "%%MatrixMarket matrix array real general\n{} 2\n",
+        usize::MAX
+    );
+    let error = CsrMatrix::<f64>::read_matrix_market(Cursor::new(overflow)).unwrap_err();
+    assert!(matches!(error, ReadError::Parse { line: 2, .. }));
+}
+
+#[test]
+fn binary_round_trip() {
+    let m = random_matrix(&mut StdRng::seed_from_u64(112), 7, 4) * 1e6;
+    let mut buffer = Vec::new();
+    m.write_binary(&mut buffer).unwrap();
+    assert_eq!(buffer.len(), 21 + 7 * 4 * 4);
+    assert_eq!(&buffer[..5], b"RGMX\x04");
+    assert_eq!(Matrix::read_binary(buffer.as_slice()).unwrap(), m);
+
+    let wide = m.map(|x| x as f64 / 3.0);
+    let mut buffer = Vec::new();
+    wide.write_binary(&mut buffer).unwrap();
+    assert_eq!(buffer[4], 8);
+    assert_eq!(GenericMatrix::read_binary(buffer.as_slice()).unwrap(), wide);
+
+    let empty = Matrix::new(0, 3);
+    let mut buffer = Vec::new();
+    empty.write_binary(&mut buffer).unwrap();
+    assert_eq!(Matrix::read_binary(buffer.as_slice()).unwrap(), empty);
+}
+
+#[test]
+fn binary_errors() {
+    let mut buffer = Vec::new();
+    Matrix::identity(2).write_binary(&mut buffer).unwrap();
+
+    let offset = |bytes: &[u8]| match Matrix::read_binary(bytes) {
+        Err(ReadError::Corrupt { offset, .. }) => offset,
+        other => panic!("expected corrupt input, found {:?}", other),
+    };
+
+    assert_eq!(offset(&buffer[..10]), 0);
+    assert_eq!(offset(&buffer[..buffer.len() - 1]), 21);
+
+    let mut bad_magic = buffer.clone();
+    bad_magic[0] = b'X';
+    assert_eq!(offset(&bad_magic), 0);
+
+    let mut bad_width = buffer.clone();
+    bad_width[4] = 2;
+    assert_eq!(offset(&bad_width), 4);
+
+    let mut huge = buffer.clone();
+    huge[5..21].copy_from_slice(&[0xFF; 16]);
+    assert_eq!(offset(&huge), 5);
+
+    // A header claiming far more data than the file holds is reported, not
+    // allocated.
+    let mut truncated = buffer[..21].to_vec();
+    truncated[5..13].copy_from_slice(&(1u64 << 20).to_le_bytes());
+    truncated[13..21].copy_from_slice(&(1u64 << 20).to_le_bytes());
+    assert_eq!(offset(&truncated), 21);
+    truncated.extend_from_slice(&[0; 12]);
+    assert_eq!(offset(&truncated), 21);
+}
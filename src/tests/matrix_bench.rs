@@ -0,0 +1,97 @@
+//! Matrix multiply benchmarks.
+//!
+//! Ignored by default. Run with optimisations and captured output:
+//!
+//! ```text
+//! cargo test --release matrix_multiply_benchmark -- --ignored --nocapture
+//! ```
+//!
+//! Inputs come from a fixed seed so every run multiplies the same matrices.
+//! Each timing is the median of several samples after one warm-up run.
+
+use crate::tests::helpers::random_matrix;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const SEED: u64 = 0x5EED;
+const SIZES: [usize; 6] = [4, 16, 64, 128, 256, 1024];
+
+/// Enough repetitions per sample that small sizes are not dominated by timer
+/// resolution, while 1024x1024 still runs once.
+fn repetitions(size: usize) -> u32 {
+    return (1 << 24) / (size * size * size).max(1) as u32 + 1;
+}
+
+fn median_time(size: usize, mut f: impl FnMut()) -> Duration {
+    let samples = if size >= 1024 { 3 } else { 7 };
+    let reps = repetitions(size);
+
+    f();
+
+    let mut times: Vec<Duration> = (0..samples)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..reps {
+                f();
+            }
+            return start.elapsed() / reps;
+        })
+        .collect();
+    times.sort();
+
+    return times[times.len() / 2];
+}
+
+#[test]
+#[ignore]
+fn matrix_multiply_benchmark() {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+
+    println!(
+        "{:>6} {:>14} {:>14} {:>14} {:>14} {:>9}",
+        "size",
+        "multiply",
+        "multiply2",
+        "blocked",
+        format!("parallel({})", threads),
+        "speed-up"
+    );
+
+    for size in SIZES {
+        let mat_a = random_matrix(&mut rng, size, size);
+        let mat_b = random_matrix(&mut rng, size, size);
+
+        let expected = mat_a.matrix_multiply(&mat_b).unwrap();
+        assert_eq!(mat_a.matrix_multiply_blocked(&mat_b).unwrap(), expected);
+        assert_eq!(
+            mat_a.matrix_multiply_parallel(&mat_b, threads).unwrap(),
+            expected
+        );
+
+        let naive = median_time(size, || {
+            std::hint::black_box(mat_a.matrix_multiply(&mat_b).unwrap());
+        });
+        let multiply2 = median_time(size, || {
+            std::hint::black_box(mat_a.matrix_multiply2(&mat_b).unwrap());
+        });
+        let blocked = median_time(size, || {
+            std::hint::black_box(mat_a.matrix_multiply_blocked(&mat_b).unwrap());
+        });
+        let parallel = median_time(size, || {
+            std::hint::black_box(mat_a.matrix_multiply_parallel(&mat_b, threads).unwrap());
+        });
+
+        println!(
+            "{:>6} {:>14} {:>14} {:>14} {:>14} {:>8.1}x",
+            size,
+            format!("{:?}", naive),
+            format!("{:?}", multiply2),
+            format!("{:?}", blocked),
+            format!("{:?}", parallel),
+            naive.as_secs_f64() / blocked.min(parallel).as_secs_f64()
+        );
+    }
+}
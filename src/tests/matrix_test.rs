@@ -1,6 +1,8 @@
-use crate::matrix::Matrix;
-use rand::Rng;
-use std::time::Instant;
+use crate::matrix::compare::Tolerance;
+use crate::matrix::{Matrix, MatrixError};
+use crate::tests::helpers::{assert_near, random_matrix};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 #[allow(dead_code)]
 fn matrix_test_1() {
@@ -8,10 +10,10 @@ fn matrix_test_1() {
     let mut mat_b = Matrix::new(2, 5);
     let mut mat_c = Matrix::new(2, 5);
 
-    mat_a.set(0, 0, 413.0);
-    mat_a.set(0, 1, 55.0);
-    mat_a.set(1, 0, 2.0);
-    mat_a.set(1, 1, 27492.0);
+    mat_a.set(0, 0, 413.0).unwrap();
+    mat_a.set(0, 1, 55.0).unwrap();
+    mat_a.set(1, 0, 2.0).unwrap();
+    mat_a.set(1, 1, 27492.0).unwrap();
 
     mat_b.add(3.0);
     mat_c.add(4.0);
@@ -22,22 +24,22 @@ fn matrix_test_2() {
     let mut mat_a = Matrix::new(2, 3);
     let mut mat_b = Matrix::new(3, 2);
 
-    mat_a.set(0, 0, 1.0);
-    mat_a.set(0, 1, 2.0);
-    mat_a.set(0, 2, 3.0);
-    mat_a.set(1, 0, 4.0);
-    mat_a.set(1, 1, 5.0);
-    mat_a.set(1, 2, 6.0);
-
-    mat_b.set(0, 0, 7.0);
-    mat_b.set(0, 1, 8.0);
-    mat_b.set(1, 0, 9.0);
-    mat_b.set(1, 1, 10.0);
-    mat_b.set(2, 0, 11.0);
-    mat_b.set(2, 1, 12.0);
-
-    println!("{}", mat_a.to_string());
-    println!("{}", mat_b.to_string());
+    mat_a.set(0, 0, 1.0).unwrap();
+    mat_a.set(0, 1, 2.0).unwrap();
+    mat_a.set(0, 2, 3.0).unwrap();
+    mat_a.set(1, 0, 4.0).unwrap();
+    mat_a.set(1, 1, 5.0).unwrap();
+    mat_a.set(1, 2, 6.0).unwrap();
+
+    mat_b.set(0, 0, 7.0).unwrap();
+    mat_b.set(0, 1, 8.0).unwrap();
+    mat_b.set(1, 0, 9.0).unwrap();
+    mat_b.set(1, 1, 10.0).unwrap();
+    mat_b.set(2, 0, 11.0).unwrap();
+    mat_b.set(2, 1, 12.0).unwrap();
+
+    println!("{}", mat_a);
+    println!("{}", mat_b);
     let mat_c = mat_a.matrix_multiply2(&mat_b);
 
     Matrix::print_matrix(mat_c);
@@ -45,13 +47,14 @@ fn matrix_test_2() {
 
 #[allow(dead_code)]
 fn matrix_mult_test_1() {
-    let mat_a = Matrix::from_vec(vec![vec![1.0, 2.0, 3.0, 4.0], vec![5.0, 6.0, 7.0, 8.0]]);
+    let mat_a = Matrix::from_vec(vec![vec![1.0, 2.0, 3.0, 4.0], vec![5.0, 6.0, 7.0, 8.0]]).unwrap();
     let mat_b = Matrix::from_vec(vec![
         vec![1.0, 2.0],
         vec![1.0, 2.0],
         vec![1.0, 2.0],
         vec![1.0, 2.0],
-    ]);
+    ])
+    .unwrap();
 
     let mat_c = mat_a.matrix_multiply2(&mat_b);
     Matrix::print_matrix(mat_c);
@@ -59,14 +62,15 @@ fn matrix_mult_test_1() {
 
 #[allow(dead_code)]
 fn matrix_mult_test_2() {
-    let mat_a = Matrix::from_vec(vec![vec![9.0, 2.0, 12.0, 4.0], vec![2.0, 8.0, 21.0, 55.0]]);
-    let mat_b = Matrix::from_vec(vec![vec![7.0], vec![2.0], vec![92.0], vec![3.0]]);
+    let mat_a =
+        Matrix::from_vec(vec![vec![9.0, 2.0, 12.0, 4.0], vec![2.0, 8.0, 21.0, 55.0]]).unwrap();
+    let mat_b = Matrix::from_vec(vec![vec![7.0], vec![2.0], vec![92.0], vec![3.0]]).unwrap();
     let mat_c = mat_a.matrix_multiply2(&mat_b);
 
     Matrix::print_matrix(mat_c);
 
-    let mat_a = Matrix::from_vec(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
-    let mat_b = Matrix::from_vec(vec![vec![4.0], vec![5.0], vec![6.0]]);
+    let mat_a = Matrix::from_vec(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]).unwrap();
+    let mat_b = Matrix::from_vec(vec![vec![4.0], vec![5.0], vec![6.0]]).unwrap();
     let mat_c = mat_a.matrix_multiply2(&mat_b);
 
     Matrix::print_matrix(mat_c);
@@ -78,12 +82,14 @@ fn matrix_mult_test_3() {
         vec![3.0, 3.0, 3.0],
         vec![4.0, 4.0, 4.0],
         vec![5.0, 5.0, 5.0],
-    ]);
+    ])
+    .unwrap();
     let mat_b = Matrix::from_vec(vec![
         vec![4.0, 7.0, 5.0],
         vec![5.0, 8.0, 6.0],
         vec![6.0, 9.0, 7.0],
-    ]);
+    ])
+    .unwrap();
     let mat_c = mat_a.matrix_multiply2(&mat_b);
 
     Matrix::print_matrix(mat_c);
@@ -92,8 +98,9 @@ fn matrix_mult_test_3() {
         vec![3.0, 3.0, 3.0],
         vec![4.0, 4.0, 4.0],
         vec![5.0, 5.0, 5.0],
-    ]);
-    let mat_b = Matrix::from_vec(vec![vec![4.0, 7.0], vec![5.0, 8.0], vec![6.0, 9.0]]);
+    ])
+    .unwrap();
+    let mat_b = Matrix::from_vec(vec![vec![4.0, 7.0], vec![5.0, 8.0], vec![6.0, 9.0]]).unwrap();
     let mat_c = mat_a.matrix_multiply2(&mat_b);
 
     Matrix::print_matrix(mat_c);
@@ -106,20 +113,20 @@ fn matrix_equality_test_1() {
     let b: f32 = rng.gen::<f32>() * 100.0;
     let c: f32 = rng.gen::<f32>() * 100.0;
 
-    let mat_a = Matrix::from_vec(vec![vec![a, b, c], vec![b, c, a], vec![c, a, b]]);
-    let mat_b = Matrix::from_vec(vec![vec![c, b, a], vec![a, c, b], vec![b, a, c]]);
+    let mat_a = Matrix::from_vec(vec![vec![a, b, c], vec![b, c, a], vec![c, a, b]]).unwrap();
+    let mat_b = Matrix::from_vec(vec![vec![c, b, a], vec![a, c, b], vec![b, a, c]]).unwrap();
 
     let mat_c = mat_a.matrix_multiply(&mat_b).unwrap();
     let mat_d = mat_a.matrix_multiply2(&mat_b).unwrap();
 
-    if !mat_c.equals(&mat_d) {
-        println!("MISMATCH FOUND: ");
-        println!("Matrix A: {}", mat_a.to_string());
-        println!("Matrix B: {}", mat_b.to_string());
+    if !mat_c.approx_eq(&mat_d, Tolerance::Relative(4.0 * f32::EPSILON)) {
+        println!("MISMATCH FOUND: {}", mat_c.max_difference(&mat_d).unwrap());
+        println!("Matrix A: {}", mat_a);
+        println!("Matrix B: {}", mat_b);
         println!("================================");
 
-        println!("Matrix mutliply 1: {}", mat_c.to_string());
-        println!("Matrix mutliply 2: {}", mat_d.to_string());
+        println!("Matrix mutliply 1: {}", mat_c);
+        println!("Matrix mutliply 2: {}", mat_d);
     } else {
         println!("Matrix multiplication successful");
     }
@@ -139,13 +146,13 @@ fn matrix_equality_test_2() {
     for i in 0..mat_a_rows * mat_size {
         let rand = rng.gen::<f32>() * 100.0;
         let (x, y) = Matrix::get_coords(i, mat_a_rows, mat_size).unwrap_or((0, 0));
-        mat_a.set(x, y, rand);
+        mat_a.set(x, y, rand).unwrap();
     }
 
     for i in 0..mat_size * mat_b_cols {
         let rand = rng.gen::<f32>() * 100.0;
         let (x, y) = Matrix::get_coords(i, mat_size, mat_b_cols).unwrap_or((0, 0));
-        mat_b.set(x, y, rand);
+        mat_b.set(x, y, rand).unwrap();
     }
 
     let mat_c = mat_a.matrix_multiply(&mat_b).unwrap();
@@ -153,83 +160,204 @@ fn matrix_equality_test_2() {
 
     if !mat_c.equals(&mat_d) {
         println!("MISMATCH FOUND: ");
-        println!("Matrix A: {}", mat_a.to_string());
-        println!("Matrix B: {}", mat_b.to_string());
+        println!("Matrix A: {}", mat_a);
+        println!("Matrix B: {}", mat_b);
         println!("================================");
 
-        println!("Matrix mutliply 1: {}", mat_c.to_string());
-        println!("Matrix mutliply 2: {}", mat_d.to_string());
+        println!("Matrix mutliply 1: {}", mat_c);
+        println!("Matrix mutliply 2: {}", mat_d);
     } else {
         println!("Matrix multiplication successful");
     }
 }
 
-fn matrix_mult_1_rand_test() {
-    let mut rng = rand::thread_rng();
+fn random_well_conditioned(rng: &mut StdRng, size: usize) -> Matrix {
+    let mut mat = random_matrix(rng, size, size);
 
-    let mat_a_rows = rng.gen_range(1..5);
-    let mat_size = rng.gen_range(1..5);
-    let mat_b_cols = rng.gen_range(1..5);
+    for i in 0..size {
+        // Diagonal dominance keeps the matrix far from singular.
+        mat.set(i, i, mat.at(i, i).unwrap() + size as f32 + 1.0)
+            .unwrap();
+    }
 
-    let mut mat_a = Matrix::new(mat_a_rows, mat_size);
-    let mut mat_b = Matrix::new(mat_size, mat_b_cols);
+    return mat;
+}
 
-    for i in 0..mat_a_rows * mat_size {
-        let rand = rng.gen::<f32>() * 100.0;
-        let (x, y) = Matrix::get_coords(i, mat_a_rows, mat_size).unwrap_or((0, 0));
-        mat_a.set(x, y, rand);
-    }
+#[test]
+fn inverse_of_random_matrices() {
+    let mut rng = StdRng::seed_from_u64(1);
 
-    for i in 0..mat_size * mat_b_cols {
-        let rand = rng.gen::<f32>() * 100.0;
-        let (x, y) = Matrix::get_coords(i, mat_size, mat_b_cols).unwrap_or((0, 0));
-        mat_b.set(x, y, rand);
+    for size in 1..9 {
+        for _ in 0..20 {
+            let mat = random_well_conditioned(&mut rng, size);
+            let inv = mat.inverse().unwrap();
+
+            let product = mat.matrix_multiply(&inv).unwrap();
+            assert_near(&product, &Matrix::identity(size), 1e-5);
+
+            let product = inv.matrix_multiply(&mat).unwrap();
+            assert_near(&product, &Matrix::identity(size), 1e-5);
+        }
     }
+}
 
-    let _mat_c = mat_a.matrix_multiply(&mat_b).unwrap();
+#[test]
+fn inverse_needs_pivoting() {
+    let mat = Matrix::from_vec(vec![
+        vec![0.0, 1.0, 2.0],
+        vec![1.0, 0.0, 3.0],
+        vec![4.0, -3.0, 8.0],
+    ])
+    .unwrap();
+    let expected = Matrix::from_vec(vec![
+        vec![-4.5, 7.0, -1.5],
+        vec![-2.0, 4.0, -1.0],
+        vec![1.5, -2.0, 0.5],
+    ])
+    .unwrap();
+
+    assert_near(&mat.inverse().unwrap(), &expected, 1e-5);
 }
 
-fn matrix_mult_2_rand_test() {
-    let mut rng = rand::thread_rng();
+#[test]
+fn inverse_errors() {
+    let singular = Matrix::from_vec(vec![vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap();
+    assert_eq!(singular.inverse().unwrap_err(), MatrixError::Singular);
+
+    let rect = Matrix::new(2, 3);
+    assert_eq!(
+        rect.inverse().unwrap_err(),
+        MatrixError::NotSquare { rows: 2, cols: 3 }
+    );
+    assert!(rect.determinant().is_err());
+}
 
-    let mat_a_rows = rng.gen_range(1..5);
-    let mat_size = rng.gen_range(1..5);
-    let mat_b_cols = rng.gen_range(1..5);
+#[test]
+fn determinant_values() {
+    let mat = Matrix::from_vec(vec![
+        vec![0.0, 1.0, 2.0],
+        vec![1.0, 0.0, 3.0],
+        vec![4.0, -3.0, 8.0],
+    ])
+    .unwrap();
+    assert!((mat.determinant().unwrap() - -2.0).abs() < 1e-5);
 
-    let mut mat_a = Matrix::new(mat_a_rows, mat_size);
-    let mut mat_b = Matrix::new(mat_size, mat_b_cols);
+    let singular = Matrix::from_vec(vec![vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap();
+    assert_eq!(singular.determinant().unwrap(), 0.0);
 
-    for i in 0..mat_a_rows * mat_size {
-        let rand = rng.gen::<f32>() * 100.0;
-        let (x, y) = Matrix::get_coords(i, mat_a_rows, mat_size).unwrap_or((0, 0));
-        mat_a.set(x, y, rand);
-    }
+    assert_eq!(Matrix::identity(4).determinant().unwrap(), 1.0);
+}
 
-    for i in 0..mat_size * mat_b_cols {
-        let rand = rng.gen::<f32>() * 100.0;
-        let (x, y) = Matrix::get_coords(i, mat_size, mat_b_cols).unwrap_or((0, 0));
-        mat_b.set(x, y, rand);
-    }
+#[test]
+fn rank_values() {
+    assert_eq!(Matrix::identity(4).rank(), 4);
+    assert_eq!(Matrix::new(3, 3).rank(), 0);
+
+    let mat = Matrix::from_vec(vec![
+        vec![1.0, 2.0, 3.0],
+        vec![2.0, 4.0, 6.0],
+        vec![1.0, 0.0, 1.0],
+    ])
+    .unwrap();
+    assert_eq!(mat.rank(), 2);
+
+    let wide = Matrix::from_vec(vec![vec![1.0, 2.0, 3.0, 4.0], vec![2.0, 4.0, 6.0, 8.0]]).unwrap();
+    assert_eq!(wide.rank(), 1);
+}
 
-    let _mat_d = mat_a.matrix_multiply2(&mat_b).unwrap();
+#[test]
+fn matrix_api_errors() {
+    assert_eq!(
+        Matrix::from_vec(vec![]).unwrap_err(),
+        MatrixError::EmptyInput
+    );
+    assert_eq!(
+        Matrix::from_vec(vec![vec![1.0, 2.0], vec![3.0]]).unwrap_err(),
+        MatrixError::RaggedRows {
+            row: 1,
+            expected: 2,
+            found: 1
+        }
+    );
+
+    let mut mat = Matrix::new(2, 3);
+    let out_of_bounds = MatrixError::IndexOutOfBounds {
+        row: 0,
+        col: 3,
+        rows: 2,
+        cols: 3,
+    };
+    assert_eq!(mat.at(0, 3).unwrap_err(), out_of_bounds);
+    assert_eq!(mat.set(0, 3, 1.0).unwrap_err(), out_of_bounds);
+    assert!(mat.set(1, 2, 1.0).is_ok());
+    assert_eq!(mat.at(1, 2).unwrap(), 1.0);
+
+    let other = Matrix::new(2, 3);
+    assert_eq!(
+        mat.matrix_multiply(&other).unwrap_err(),
+        MatrixError::DimensionMismatch {
+            left: (2, 3),
+            right: (2, 3)
+        }
+    );
+    assert!(mat.matrix_multiply2(&other).is_err());
+    assert!(mat.matrix_add(&Matrix::new(3, 2)).is_err());
 }
 
-fn matrix_mutliply_speed_test() {
-    let lim = 250000;
+#[test]
+fn blocked_multiply_is_bit_identical() {
+    let mut rng = StdRng::seed_from_u64(8);
+
+    for (rows, inner, cols) in [(1, 1, 1), (3, 5, 2), (4, 4, 4), (70, 130, 65), (9, 64, 17)] {
+        let mat_a = Matrix::new(rows, inner).map(|_| rng.gen::<f32>() * 100.0);
+        let mat_b = Matrix::new(inner, cols).map(|_| rng.gen::<f32>() * 100.0);
 
-    let mat_mult_1_start = Instant::now();
-    for _num in 0..lim {
-        matrix_mult_1_rand_test();
+        let expected = mat_a.matrix_multiply(&mat_b).unwrap();
+        assert_eq!(mat_a.matrix_multiply_blocked(&mat_b).unwrap(), expected);
     }
-    let mat_mult_1_end = mat_mult_1_start.elapsed();
 
-    println!("Matrix Multiply 1 Duration: {:?}", mat_mult_1_end);
+    assert!(Matrix::new(2, 3)
+        .matrix_multiply_blocked(&Matrix::new(2, 3))
+        .is_err());
+}
+
+#[test]
+fn parallel_multiply_is_bit_identical() {
+    let mut rng = StdRng::seed_from_u64(9);
+
+    for (rows, inner, cols) in [(1, 1, 1), (3, 5, 2), (70, 130, 65), (200, 64, 4)] {
+        let mat_a = Matrix::new(rows, inner).map(|_| rng.gen::<f32>() * 100.0);
+        let mat_b = Matrix::new(inner, cols).map(|_| rng.gen::<f32>() * 100.0);
 
-    let mat_mult_2_start = Instant::now();
-    for _num in 0..lim {
-        matrix_mult_2_rand_test();
+        let expected = mat_a.matrix_multiply_blocked(&mat_b).unwrap();
+        for threads in [0, 1, 2, 3, 8, rows + 5] {
+            let result = mat_a.matrix_multiply_parallel(&mat_b, threads).unwrap();
+            assert_eq!(result, expected);
+        }
     }
-    let mat_mult_2_end = mat_mult_2_start.elapsed();
 
-    println!("Matrix Multiply 2 Duration: {:?}", mat_mult_2_end);
+    assert_eq!(
+        Matrix::new(0, 3)
+            .matrix_multiply_parallel(&Matrix::new(3, 2), 4)
+            .unwrap(),
+        Matrix::new(0, 2)
+    );
+    assert!(Matrix::new(2, 3)
+        .matrix_multiply_parallel(&Matrix::new(2, 3), 4)
+        .is_err());
+}
+
+#[test]
+fn row_major_construction() {
+    let m = Matrix::from_row_major(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    assert_eq!(m, Matrix::from_array([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]));
+    assert_eq!(m.as_slice(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    assert_eq!(
+        Matrix::from_row_major(2, 2, vec![1.0]),
+        Err(MatrixError::DataLength {
+            expected: 4,
+            found: 1
+        })
+    );
 }
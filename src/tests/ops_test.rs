@@ -0,0 +1,89 @@
+use crate::matrix::Matrix;
+
+fn mat_a() -> Matrix {
+    return Matrix::from_array([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+}
+
+fn mat_b() -> Matrix {
+    return Matrix::from_array([[7.0, 8.0], [9.0, 10.0], [11.0, 12.0]]);
+}
+
+#[test]
+fn multiply_operators_match_method() {
+    let expected = mat_a().matrix_multiply(&mat_b()).unwrap();
+
+    assert_eq!(mat_a() * mat_b(), expected);
+    assert_eq!(&mat_a() * &mat_b(), expected);
+    assert_eq!(mat_a() * &mat_b(), expected);
+    assert_eq!(&mat_a() * mat_b(), expected);
+
+    let mut c = mat_a();
+    c *= mat_b();
+    assert_eq!(c, expected);
+}
+
+#[test]
+fn add_sub_neg_operators() {
+    let a = mat_a();
+    let b = Matrix::from_array([[1.0, 1.0, 1.0], [2.0, 2.0, 2.0]]);
+
+    assert_eq!(
+        &a + &b,
+        Matrix::from_array([[2.0, 3.0, 4.0], [6.0, 7.0, 8.0]])
+    );
+    assert_eq!(
+        &a - &b,
+        Matrix::from_array([[0.0, 1.0, 2.0], [2.0, 3.0, 4.0]])
+    );
+    assert_eq!(-&a + &a, Matrix::new(2, 3));
+    assert_eq!(a.clone() - a.clone(), Matrix::new(2, 3));
+
+    let mut c = a.clone();
+    c += &b;
+    c -= b;
+    assert_eq!(c, a);
+}
+
+#[test]
+fn scalar_operators() {
+    let a = mat_a();
+    let doubled = Matrix::from_array([[2.0, 4.0, 6.0], [8.0, 10.0, 12.0]]);
+
+    assert_eq!(&a * 2.0, doubled);
+    assert_eq!(2.0 * &a, doubled);
+    assert_eq!(&doubled / 2.0, a);
+
+    let mut c = a.clone();
+    c *= 2.0;
+    assert_eq!(c, doubled);
+    c /= 2.0;
+    assert_eq!(c, a);
+}
+
+#[test]
+fn index_operators() {
+    let mut a = mat_a();
+    assert_eq!(a[(1, 2)], 6.0);
+
+    a[(0, 1)] = 20.0;
+    assert_eq!(a.at(0, 1).unwrap(), 20.0);
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch: 2x3 and 2x3")]
+fn multiply_shape_mismatch_panics() {
+    let _ = mat_a() * mat_a();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn add_shape_mismatch_panics() {
+    let mut a = mat_a();
+    a += mat_b();
+}
+
+#[test]
+#[should_panic(expected = "index (2, 0) out of bounds for 2x3 matrix")]
+fn index_out_of_bounds_panics() {
+    let _ = mat_a()[(2, 0)];
+}
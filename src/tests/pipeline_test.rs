@@ -0,0 +1,211 @@
+use crate::drawline::{
+    create_scene, create_screen_scene, get_clip_matrix, get_clipping_coordinates,
+    get_perspective_matrix, get_projection_matrix, get_screen_coordinates, get_view_matrix,
+};
+use crate::matrix::fixed::Matrix4;
+use crate::solid::{
+    GetLines, Perspective, Point, Projection, Scene, ScreenParameter, ScreenVertex,
+};
+use crate::transformations::{rotate, translate, RotationAxis};
+use crate::vector::Vec4;
+
+fn parameters() -> ScreenParameter {
+    return ScreenParameter::new([0.0, 4.0, 100.0], 60.0, 30.0, 400.0, 400.0, 0.0, 0.0);
+}
+
+#[test]
+fn apply_matrix_divides_by_w() {
+    // Puts z into w, like a perspective projection.
+    let mut project = Matrix4::identity();
+    project[(2, 3)] = 1.0;
+    project[(3, 3)] = 0.0;
+
+    let p = Point::from_f32(2.0, 4.0, 8.0);
+    assert_eq!(
+        p.apply_matrix_homogeneous(&project),
+        Vec4::new(2.0, 4.0, 8.0, 8.0)
+    );
+    let divided = p.apply_matrix(&project);
+    assert_eq!((divided.x, divided.y, divided.z), (0.25, 0.5, 1.0));
+
+    let refs = [&p];
+    assert_eq!(
+        Point::apply_matrix_batch_homogeneous(&refs, &project),
+        vec![p.apply_matrix_homogeneous(&project)]
+    );
+
+    // Affine matrices keep w = 1, so nothing changes for them.
+    let moved = p.apply_matrix(&translate(1.0, 1.0, 1.0));
+    assert_eq!((moved.x, moved.y, moved.z), (3.0, 5.0, 9.0));
+}
+
+#[test]
+fn rotations_about_x_and_y_keep_w() {
+    for axis in [RotationAxis::X, RotationAxis::Y, RotationAxis::Z] {
+        let r = rotate(axis, 0.3, 1.0, 2.0, 3.0);
+        assert_eq!(r[(3, 3)], 1.0);
+
+        let moved = Point::from_f32(1.0, 2.0, 3.0).apply_matrix_homogeneous(&r);
+        assert!((moved.x - 1.0).abs() < 1e-6);
+        assert!((moved.y - 2.0).abs() < 1e-6);
+        assert!((moved.z - 3.0).abs() < 1e-6);
+        assert_eq!(moved.w, 1.0);
+    }
+}
+
+#[test]
+fn legacy_clip_matrix_divides_by_view_depth() {
+    let eye = [0.0, 4.0, 100.0];
+    let view_angle = parameters().get_view_angle();
+    let view = get_view_matrix(eye[0], eye[1], eye[2]);
+    let clip = get_clip_matrix(view_angle);
+
+    let p = Point::from_f32(10.0, -5.0, 20.0);
+    let v = p.apply_matrix(&view);
+    let c = p.apply_matrix_homogeneous(&(view * clip));
+    assert_eq!(c.w, v.z);
+
+    let vertex = ScreenVertex::from_clip(c, 400.0, 400.0, 0.0, 0.0);
+    let expected_x = (v.x * view_angle / v.z) * 400.0;
+    let expected_y = (v.y * view_angle / v.z) * 400.0;
+    assert!((vertex.position.x - expected_x).abs() < 1e-3);
+    assert!((vertex.position.y - expected_y).abs() < 1e-3);
+    assert_eq!(vertex.w, v.z);
+}
+
+#[test]
+fn perspective_correct_interpolation() {
+    let a = Vec4::new(-1.0, 0.0, 1.0, 2.0);
+    let b = Vec4::new(3.0, 2.0, 5.0, 10.0);
+    let (sa, sb) = (
+        ScreenVertex::from_clip(a, 1.0, 1.0, 0.0, 0.0),
+        ScreenVertex::from_clip(b, 1.0, 1.0, 0.0, 0.0),
+    );
+
+    assert_eq!(sa.perspective_parameter(&sb, 0.0), 0.0);
+    assert_eq!(sa.perspective_parameter(&sb, 1.0), 1.0);
+
+    for t in [0.1, 0.5, 0.8] {
+        // The 3D point at parameter s projects onto the screen point at t.
+        let s = sa.perspective_parameter(&sb, t);
+        let on_segment = ScreenVertex::from_clip(a.lerp(b, s), 1.0, 1.0, 0.0, 0.0);
+        let on_screen = sa.position.lerp(sb.position, t);
+
+        assert!((on_segment.position - on_screen).length() < 1e-5);
+        assert!((on_segment.depth - (sa.depth + (sb.depth - sa.depth) * t)).abs() < 1e-5);
+    }
+
+    let same_w = ScreenVertex::from_clip(Vec4::new(0.0, 0.0, 0.0, 4.0), 1.0, 1.0, 0.0, 0.0);
+    let other = ScreenVertex::from_clip(Vec4::new(1.0, 0.0, 0.0, 4.0), 1.0, 1.0, 0.0, 0.0);
+    assert!((same_w.perspective_parameter(&other, 0.3) - 0.3).abs() < 1e-6);
+}
+
+#[test]
+fn pipeline_keeps_w_until_the_divide() {
+    let origin = Point::from_f32(0.0, 0.0, 0.0);
+    let cube = crate::solid::cube::Cube::new(40.0, &origin);
+    let lines = cube.get_lines();
+    let scene = Scene {
+        num_lines: lines.len(),
+        lines,
+    };
+    let params = parameters();
+
+    let eye = params.get_eye_coordinates();
+    let clip = get_view_matrix(eye[0], eye[1], eye[2]) * get_clip_matrix(params.get_view_angle());
+    let clipped = get_clipping_coordinates(&clip, &scene);
+    assert_eq!(clipped.num_lines, scene.num_lines);
+    assert!(clipped
+        .lines
+        .iter()
+        .all(|line| line.a.w > 0.0 && line.b.w > 0.0));
+
+    let (vsx, vsy, vcx, vcy) = params.get_view_data();
+    let screen = get_screen_coordinates(&clipped, vsx, vsy, vcx, vcy);
+    for (clip, vertex) in clipped.lines.iter().zip(&screen.lines) {
+        assert_eq!(vertex.a.w, clip.a.w);
+        assert_eq!(vertex.a.position.x, clip.a.x / clip.a.w * vsx + vcx);
+    }
+
+    let vertices = create_screen_scene(
+        Scene {
+            num_lines: scene.num_lines,
+            lines: cube.get_lines(),
+        },
+        &params,
+    );
+    let points = create_scene(scene, &params);
+    for (v, p) in vertices.lines.iter().zip(&points.lines) {
+        assert_eq!(v.a.position, *p.a);
+        assert_eq!(v.b.position, *p.b);
+    }
+}
+
+#[test]
+fn perspective_maps_the_frustum_to_clip_space() {
+    let perspective = Perspective::new(1.2, 1.5, 2.0, 50.0);
+    let m = get_perspective_matrix(&perspective);
+    let half_height = |depth: f32| depth * (0.6f32).tan();
+
+    // Near and far planes land on depth -1 and 1.
+    let near = Point::from_f32(0.0, 0.0, 2.0).apply_matrix(&m);
+    let far = Point::from_f32(0.0, 0.0, 50.0).apply_matrix(&m);
+    assert!((near.z + 1.0).abs() < 1e-5);
+    assert!((far.z - 1.0).abs() < 1e-5);
+
+    // The edges of the field of view land on the edges of the clip volume.
+    for depth in [2.0, 10.0, 50.0] {
+        let corner = Point::from_f32(1.5 * half_height(depth), half_height(depth), depth);
+        let clip = corner.apply_matrix_homogeneous(&m);
+        assert_eq!(clip.w, depth);
+        assert!((clip.x / clip.w - 1.0).abs() < 1e-5);
+        assert!((clip.y / clip.w - 1.0).abs() < 1e-5);
+    }
+
+    // Depth increases monotonically between the planes.
+    let depths = [3.0, 5.0, 20.0, 40.0].map(|z| Point::from_f32(0.0, 0.0, z).apply_matrix(&m).z);
+    assert!(depths.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(depths.iter().all(|d| d.abs() < 1.0));
+}
+
+#[test]
+fn legacy_projection_is_the_default() {
+    let params = parameters();
+    assert_eq!(params.get_projection(), Projection::Legacy);
+    assert_eq!(
+        get_projection_matrix(&params),
+        get_clip_matrix(params.get_view_angle())
+    );
+
+    let perspective = Perspective::new(1.0, 1.0, 1.0, 500.0);
+    let params = parameters().with_projection(Projection::Perspective(perspective));
+    assert_eq!(
+        get_projection_matrix(&params),
+        get_perspective_matrix(&perspective)
+    );
+}
+
+#[test]
+fn perspective_pipeline_normalises_depth() {
+    let origin = Point::from_f32(0.0, 0.0, 0.0);
+    let cube = crate::solid::cube::Cube::new(40.0, &origin);
+    let perspective = Perspective::new(1.6, 1.0, 10.0, 500.0);
+    let params = parameters().with_projection(Projection::Perspective(perspective));
+
+    let lines = cube.get_lines();
+    let vertices = create_screen_scene(
+        Scene {
+            num_lines: lines.len(),
+            lines,
+        },
+        &params,
+    );
+
+    assert_eq!(vertices.num_lines, 12);
+    for line in &vertices.lines {
+        for v in [*line.a, *line.b] {
+            assert!(v.depth > -1.0 && v.depth < 1.0);
+            assert!(v.position.x.abs() <= 400.0 && v.position.y.abs() <= 400.0);
+        }
+    }
+}
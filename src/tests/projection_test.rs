@@ -0,0 +1,233 @@
+use std::f32::consts::{FRAC_PI_4, FRAC_PI_6};
+
+use nannou::geom::Point2;
+
+use crate::drawline::{
+    create_multiview, get_camera_matrix, get_look_at_matrix, get_orthographic_matrix,
+    get_projection_matrix, get_view_matrix, MultiView, ViewArrangement,
+};
+use crate::matrix::compare::Tolerance;
+use crate::matrix::fixed::Matrix4;
+use crate::solid::cube::Cube;
+use crate::solid::{GetLines, Line, Orthographic, Point, Projection, Scene, ScreenParameter};
+use crate::vector::Vec3;
+
+fn parameters(projection: Projection) -> ScreenParameter {
+    return ScreenParameter::new([30.0, -40.0, 50.0], 60.0, 30.0, 400.0, 400.0, 0.0, 0.0)
+        .with_projection(projection);
+}
+
+fn volume() -> Orthographic {
+    return Orthographic::new(100.0, 100.0, 1.0, 200.0);
+}
+
+fn world_to_ndc(params: &ScreenParameter) -> Matrix4 {
+    return get_camera_matrix(params) * get_projection_matrix(params);
+}
+
+/// Screen-space length of each world axis.
+fn axis_lengths(projection: Projection) -> [f32; 3] {
+    let m = world_to_ndc(&parameters(projection));
+    let origin = Vec3::from(Point::from_f32(0.0, 0.0, 0.0).apply_matrix(&m));
+
+    return [
+        Point::from_f32(10.0, 0.0, 0.0),
+        Point::from_f32(0.0, 10.0, 0.0),
+        Point::from_f32(0.0, 0.0, 10.0),
+    ]
+    .map(|p| {
+        let v = Vec3::from(p.apply_matrix(&m)) - origin;
+        return Vec3::new(v.x, v.y, 0.0).length();
+    });
+}
+
+#[test]
+fn look_at_matches_the_legacy_view_matrix() {
+    for eye in [
+        Vec3::new(30.0, -40.0, 50.0),
+        Vec3::new(0.0, 4.0, 100.0),
+        Vec3::new(-7.0, 2.0, -3.0),
+    ] {
+        let look_at = get_look_at_matrix(eye, Vec3::zero(), Vec3::new(0.0, 0.0, 1.0));
+        let legacy = get_view_matrix(eye.x, eye.y, eye.z);
+        assert!(look_at.approx_eq(&legacy, Tolerance::Absolute(1e-4)));
+    }
+
+    // Looking straight down still gives a rotation.
+    let down = get_look_at_matrix(
+        Vec3::new(0.0, 0.0, 10.0),
+        Vec3::zero(),
+        Vec3::new(0.0, 0.0, 1.0),
+    );
+    let origin = Point::from_f32(0.0, 0.0, 0.0).apply_matrix(&down);
+    assert_eq!((origin.x, origin.y, origin.z), (0.0, 0.0, 10.0));
+}
+
+#[test]
+fn orthographic_box_maps_to_clip_volume() {
+    let m = get_orthographic_matrix(&Orthographic::new(20.0, 10.0, 2.0, 12.0));
+
+    let near = Point::from_f32(10.0, 5.0, 2.0).apply_matrix_homogeneous(&m);
+    let far = Point::from_f32(-10.0, -5.0, 12.0).apply_matrix_homogeneous(&m);
+    assert!(Vec3::new(near.x, near.y, near.z).distance(Vec3::new(1.0, 1.0, -1.0)) < 1e-6);
+    assert!(Vec3::new(far.x, far.y, far.z).distance(Vec3::new(-1.0, -1.0, 1.0)) < 1e-6);
+    assert_eq!((near.w, far.w), (1.0, 1.0));
+}
+
+#[test]
+fn orthographic_size_does_not_depend_on_depth() {
+    let m = world_to_ndc(&parameters(Projection::Orthographic(volume())));
+
+    let width = |offset: Vec3| {
+        let a = Point::from(offset).apply_matrix(&m);
+        let b = Point::from(offset + Vec3::new(0.0, 0.0, 10.0)).apply_matrix(&m);
+        return Vec3::new(a.x - b.x, a.y - b.y, 0.0).length();
+    };
+    let near = width(Vec3::new(10.0, -10.0, 10.0));
+    let far = width(Vec3::new(-20.0, 20.0, -20.0));
+    assert!((near - far).abs() < 1e-5);
+}
+
+#[test]
+fn axonometric_modes_foreshorten_axes() {
+    let [x, y, z] = axis_lengths(Projection::Isometric(volume()));
+    assert!((x - y).abs() < 1e-5 && (y - z).abs() < 1e-5);
+    // Each axis is foreshortened to sqrt(2/3) of its length.
+    assert!((x - 0.2 * (2.0f32 / 3.0).sqrt()).abs() < 1e-5);
+
+    let [x, y, z] = axis_lengths(Projection::Dimetric {
+        volume: volume(),
+        elevation: 0.3,
+    });
+    assert!((x - y).abs() < 1e-5);
+    assert!((x - z).abs() > 1e-3);
+
+    let [x, y, z] = axis_lengths(Projection::Trimetric {
+        volume: volume(),
+        azimuth: 0.4,
+        elevation: 0.7,
+    });
+    assert!((x - y).abs() > 1e-3 && (y - z).abs() > 1e-3 && (x - z).abs() > 1e-3);
+}
+
+#[test]
+fn oblique_modes_keep_the_front_face_true() {
+    let cavalier = Projection::Cavalier {
+        volume: volume(),
+        angle: FRAC_PI_4,
+    };
+    let cabinet = Projection::Cabinet {
+        volume: volume(),
+        angle: FRAC_PI_6,
+    };
+
+    let [x, y, z] = axis_lengths(cavalier);
+    assert!((x - 0.2).abs() < 1e-5 && (z - 0.2).abs() < 1e-5);
+    assert!((y - 0.2).abs() < 1e-5);
+
+    let [x, y, z] = axis_lengths(cabinet);
+    assert!((x - 0.2).abs() < 1e-5 && (z - 0.2).abs() < 1e-5);
+    assert!((y - 0.1).abs() < 1e-5);
+
+    // Receding lines leave at the given angle.
+    let m = world_to_ndc(&parameters(cabinet));
+    let a = Point::from_f32(0.0, 0.0, 0.0).apply_matrix(&m);
+    let b = Point::from_f32(0.0, 10.0, 0.0).apply_matrix(&m);
+    assert!(((b.y - a.y).atan2(b.x - a.x) - FRAC_PI_6).abs() < 1e-4);
+
+    // Points on the picture plane are not moved.
+    let p = Point::from_f32(10.0, 0.0, -5.0).apply_matrix(&m);
+    assert!((p.x - 0.2).abs() < 1e-5 && (p.y + 0.1).abs() < 1e-5);
+}
+
+fn cube_views(arrangement: ViewArrangement) -> MultiView {
+    let origin = Point::from_f32(-10.0, -10.0, -10.0);
+    let cube = Cube::new(20.0, &origin);
+    let lines = cube.get_lines();
+    let scene = Scene {
+        num_lines: lines.len(),
+        lines,
+    };
+
+    return create_multiview(
+        &scene,
+        Vec3::zero(),
+        &volume(),
+        arrangement,
+        &parameters(Projection::Legacy),
+    );
+}
+
+fn centre(view: &Scene<Point2>) -> (f32, f32) {
+    let count = (view.lines.len() * 2) as f32;
+    let sum = view.lines.iter().fold((0.0, 0.0), |(x, y), line| {
+        (x + line.a.x + line.b.x, y + line.a.y + line.b.y)
+    });
+    return (sum.0 / count, sum.1 / count);
+}
+
+#[test]
+fn multiview_layouts() {
+    let third = cube_views(ViewArrangement::ThirdAngle);
+    let (front, top, side) = (
+        centre(&third.front),
+        centre(&third.top),
+        centre(&third.side),
+    );
+    assert_eq!(front, (-200.0, -200.0));
+    assert_eq!(top, (-200.0, 200.0));
+    assert_eq!(side, (200.0, -200.0));
+
+    let first = cube_views(ViewArrangement::FirstAngle);
+    let (front, top, side) = (
+        centre(&first.front),
+        centre(&first.top),
+        centre(&first.side),
+    );
+    assert_eq!(front, (-200.0, 200.0));
+    assert_eq!(top, (-200.0, -200.0));
+    assert_eq!(side, (200.0, 200.0));
+
+    let scene = first.combined();
+    assert_eq!(scene.num_lines, 36);
+}
+
+#[test]
+fn multiview_orients_each_view() {
+    let origin = Point::from_f32(0.0, 0.0, 0.0);
+    let marker = Point::from_f32(10.0, 20.0, 30.0);
+    let scene = Scene {
+        num_lines: 1,
+        lines: vec![Line::new(&origin, &marker)],
+    };
+    let params = parameters(Projection::Legacy);
+    let views = create_multiview(
+        &scene,
+        Vec3::zero(),
+        &volume(),
+        ViewArrangement::ThirdAngle,
+        &params,
+    );
+
+    // Each view is half the viewport: 100 world units span 400 pixels.
+    let offset = |view: &Scene<Point2>| {
+        let line = &view.lines[0];
+        return ((line.b.x - line.a.x) / 4.0, (line.b.y - line.a.y) / 4.0);
+    };
+    let close = |a: (f32, f32), b: (f32, f32)| (a.0 - b.0).abs() < 1e-3 && (a.1 - b.1).abs() < 1e-3;
+
+    // Front: x right, z up. Top: x right, y up. Right side: y right, z up.
+    assert!(close(offset(&views.front), (10.0, 30.0)));
+    assert!(close(offset(&views.top), (10.0, 20.0)));
+    assert!(close(offset(&views.side), (20.0, 30.0)));
+
+    // The left-side view of first-angle drawings sees y the other way.
+    let first = create_multiview(
+        &scene,
+        Vec3::zero(),
+        &volume(),
+        ViewArrangement::FirstAngle,
+        &params,
+    );
+    assert!(close(offset(&first.side), (-20.0, 30.0)));
+}
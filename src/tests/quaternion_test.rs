@@ -0,0 +1,307 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::matrix::compare::Tolerance;
+use crate::matrix::fixed::Matrix4;
+use crate::matrix::MatrixError;
+use crate::quaternion::{EulerOrder, Quaternion};
+use crate::solid::cube::Cube;
+use crate::solid::{GetLines, Point, Transformable};
+use crate::tests::helpers::{close, same_rotation};
+use crate::transformations::{basic_scale, rotate, translate, RotationAxis};
+use crate::vector::Vec3;
+
+const TOLERANCE: Tolerance<f32> = Tolerance::Absolute(1e-5);
+
+fn random_rotation(rng: &mut StdRng) -> Quaternion {
+    let axis = Vec3::new(
+        rng.gen_range(-1.0..1.0),
+        rng.gen_range(-1.0..1.0),
+        rng.gen_range(-1.0..1.0),
+    );
+    return Quaternion::from_axis_angle(axis, rng.gen_range(-PI..PI));
+}
+
+#[test]
+fn axis_angle_matches_principal_rotations() {
+    let theta = 0.7;
+
+    let x = Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), theta);
+    let z = Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), theta);
+    assert!(x
+        .to_matrix()
+        .approx_eq(&rotate(RotationAxis::X, theta, 0.0, 0.0, 0.0), TOLERANCE));
+    assert!(z
+        .to_matrix()
+        .approx_eq(&rotate(RotationAxis::Z, theta, 0.0, 0.0, 0.0), TOLERANCE));
+
+    // `rotate` turns the other way about Y.
+    let y = Quaternion::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), -theta);
+    assert!(y
+        .to_matrix()
+        .approx_eq(&rotate(RotationAxis::Y, theta, 0.0, 0.0, 0.0), TOLERANCE));
+
+    // Right-hand rule: a quarter turn about Z takes X to Y.
+    let quarter = Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 2.0), FRAC_PI_2);
+    let v = quarter.rotate_vector(Vec3::new(1.0, 0.0, 0.0));
+    assert!(close(v, Vec3::new(0.0, 1.0, 0.0)));
+
+    assert_eq!(
+        Quaternion::from_axis_angle(Vec3::zero(), 1.0),
+        Quaternion::identity()
+    );
+}
+
+#[test]
+fn axis_angle_round_trip() {
+    let axis = Vec3::new(1.0, -2.0, 0.5).normalize().unwrap();
+    let q = Quaternion::from_axis_angle(axis, 2.5);
+
+    let (found_axis, angle) = q.to_axis_angle();
+    assert!(close(found_axis, axis));
+    assert!((angle - 2.5).abs() < 1e-5);
+
+    // The negated quaternion is the same rotation, reported the same way.
+    let (found_axis, angle) = (-q).to_axis_angle();
+    assert!(close(found_axis, axis));
+    assert!((angle - 2.5).abs() < 1e-5);
+
+    let (_, angle) = Quaternion::identity().to_axis_angle();
+    assert_eq!(angle, 0.0);
+}
+
+#[test]
+fn composition_matches_matrix_product() {
+    let mut rng = StdRng::seed_from_u64(18);
+
+    for _ in 0..20 {
+        let a = random_rotation(&mut rng);
+        let b = random_rotation(&mut rng);
+
+        let product = a.to_matrix() * b.to_matrix();
+        assert!(a.then(b).to_matrix().approx_eq(&product, TOLERANCE));
+        assert_eq!(a.then(b), b * a);
+
+        let v = Vec3::new(0.3, -1.2, 2.0);
+        assert!(close(
+            a.then(b).rotate_vector(v),
+            b.rotate_vector(a.rotate_vector(v))
+        ));
+        assert!(close(
+            (a * b).rotate_vector(v),
+            a.rotate_vector(b.rotate_vector(v))
+        ));
+
+        let mut c = a;
+        c *= b;
+        assert_eq!(c, a * b);
+    }
+
+    // `*` is the Hamilton product: ij = k and ji = -k.
+    let i = Quaternion::new(0.0, 1.0, 0.0, 0.0);
+    let j = Quaternion::new(0.0, 0.0, 1.0, 0.0);
+    assert_eq!(i * j, Quaternion::new(0.0, 0.0, 0.0, 1.0));
+    assert_eq!(j * i, Quaternion::new(0.0, 0.0, 0.0, -1.0));
+}
+
+#[test]
+fn normalize_and_inverse() {
+    let q = Quaternion::new(2.0, -1.0, 0.5, 3.0);
+
+    let unit = q.normalize().unwrap();
+    assert!((unit.norm() - 1.0).abs() < 1e-6);
+    assert!(Quaternion::new(0.0, 0.0, 0.0, 0.0).normalize().is_none());
+    assert!(Quaternion::new(0.0, 0.0, 0.0, 0.0).inverse().is_none());
+
+    let product = q.inverse().unwrap() * q;
+    assert!(same_rotation(product, Quaternion::identity()));
+    assert!((product.w - 1.0).abs() < 1e-6);
+
+    assert_eq!(unit.conjugate() * unit, unit.inverse().unwrap() * unit);
+}
+
+#[test]
+fn matrix_round_trip() {
+    let mut rng = StdRng::seed_from_u64(1018);
+
+    let mut rotations = (0..50)
+        .map(|_| random_rotation(&mut rng))
+        .collect::<Vec<Quaternion>>();
+    // Half turns exercise every branch of the conversion.
+    rotations.extend(
+        [
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(1.0, 1.0, 0.0),
+        ]
+        .map(|axis| Quaternion::from_axis_angle(axis, PI)),
+    );
+
+    for q in rotations {
+        let m = Matrix4::from(q);
+        assert!(m.orthogonality_error() < 1e-5);
+
+        let found = Quaternion::from_matrix(&m).unwrap();
+        assert!(same_rotation(found, q), "{} vs {}", found, q);
+        assert!((found.norm() - 1.0).abs() < 1e-6);
+    }
+
+    // Translation is ignored.
+    let m = rotate(RotationAxis::Z, 0.4, 3.0, -1.0, 2.0);
+    let q = Quaternion::try_from(&m).unwrap();
+    assert!(same_rotation(
+        q,
+        Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), 0.4)
+    ));
+}
+
+#[test]
+fn from_matrix_rejects_non_rotations() {
+    let scaled = basic_scale(2.0, 2.0, 2.0);
+    let mirrored = basic_scale(1.0, 1.0, -1.0);
+    let mut projected = Matrix4::identity();
+    projected[(2, 3)] = 1.0;
+
+    for m in [scaled, mirrored, projected] {
+        assert_eq!(Quaternion::from_matrix(&m), Err(MatrixError::NotRotation));
+    }
+    assert!(Quaternion::from_matrix(&translate(1.0, 2.0, 3.0)).is_ok());
+}
+
+#[test]
+fn euler_round_trip_in_every_order() {
+    let mut rng = StdRng::seed_from_u64(2018);
+
+    for order in EulerOrder::ALL {
+        for _ in 0..20 {
+            let angles = [
+                rng.gen_range(-PI..PI),
+                rng.gen_range(-1.5..1.5),
+                rng.gen_range(-PI..PI),
+            ];
+            let q = Quaternion::from_euler(order, angles);
+
+            let found = q.to_euler(order);
+            for (a, b) in found.iter().zip(angles) {
+                assert!(
+                    (a - b).abs() < 1e-3,
+                    "{:?}: {:?} vs {:?}",
+                    order,
+                    found,
+                    angles
+                );
+            }
+            assert!(same_rotation(Quaternion::from_euler(order, found), q));
+        }
+    }
+}
+
+#[test]
+fn euler_applies_first_axis_first() {
+    let angles = [0.3, -0.8, 1.1];
+    let axes = [
+        Vec3::new(0.0, 0.0, 1.0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    ];
+
+    let expected = Quaternion::from_axis_angle(axes[0], angles[0])
+        .then(Quaternion::from_axis_angle(axes[1], angles[1]))
+        .then(Quaternion::from_axis_angle(axes[2], angles[2]));
+
+    assert!(same_rotation(
+        Quaternion::from_euler(EulerOrder::ZXY, angles),
+        expected
+    ));
+}
+
+#[test]
+fn euler_at_gimbal_lock() {
+    for order in EulerOrder::ALL {
+        for b in [FRAC_PI_2, -FRAC_PI_2] {
+            let q = Quaternion::from_euler(order, [0.4, b, -1.3]);
+
+            let found = q.to_euler(order);
+            assert_eq!(found[0], 0.0);
+            assert!((found[1] - b).abs() < 1e-2);
+            assert!(same_rotation(Quaternion::from_euler(order, found), q));
+        }
+    }
+}
+
+#[test]
+fn slerp_interpolates_at_constant_speed() {
+    let axis = Vec3::new(0.0, 1.0, 1.0);
+    let a = Quaternion::from_axis_angle(axis, 0.2);
+    let b = Quaternion::from_axis_angle(axis, 2.2);
+
+    assert!(same_rotation(a.slerp(b, 0.0), a));
+    assert!(same_rotation(a.slerp(b, 1.0), b));
+
+    for t in [0.1, 0.25, 0.5, 0.9] {
+        let expected = Quaternion::from_axis_angle(axis, 0.2 + 2.0 * t);
+        assert!(same_rotation(a.slerp(b, t), expected));
+        assert!((a.slerp(b, t).norm() - 1.0).abs() < 1e-5);
+    }
+
+    // `-b` is the same rotation; slerp still takes the short way.
+    assert!(same_rotation(
+        a.slerp(-b, 0.5),
+        Quaternion::from_axis_angle(axis, 1.2)
+    ));
+
+    // Nearly equal rotations fall back to nlerp without dividing by zero.
+    let c = Quaternion::from_axis_angle(axis, 0.2001);
+    assert!(same_rotation(a.slerp(c, 0.5), a));
+}
+
+#[test]
+fn nlerp_stays_unit_and_hits_endpoints() {
+    let a = Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), 0.5);
+    let b = Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), -1.0);
+
+    assert!(same_rotation(a.nlerp(b, 0.0), a));
+    assert!(same_rotation(a.nlerp(-b, 1.0), b));
+    assert!((a.nlerp(b, 0.3).norm() - 1.0).abs() < 1e-6);
+
+    // Symmetric inputs meet in the middle.
+    let (axis, angle) = Quaternion::identity()
+        .nlerp(
+            Quaternion::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), 1.0),
+            0.5,
+        )
+        .to_axis_angle();
+    assert!(close(axis, Vec3::new(0.0, 1.0, 0.0)));
+    assert!((angle - 0.5).abs() < 1e-5);
+}
+
+#[test]
+fn rotates_points_and_solids() {
+    let q = Quaternion::from_euler(EulerOrder::YXZ, [0.3, 1.0, -0.6]);
+    let p = Point::from_f32(1.0, 2.0, -3.0);
+
+    let rotated = Vec3::from(p.apply_quaternion(q));
+    let expected = Vec3::from(p.apply_matrix(&q.to_matrix()));
+    assert!(close(rotated, expected));
+
+    let offset = Point::from_f32(0.0, 0.0, 0.0);
+    let mut cube = Cube::new(2.0, &offset);
+    let center = Point::from_f32(1.0, 1.0, 1.0);
+    let quarter = Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), FRAC_PI_2);
+    cube.apply_quaternion(quarter, &center);
+
+    // A quarter turn about the centre maps the cube onto itself, and the
+    // corner at the origin moves to (2, 0, 0).
+    let lines = cube.get_lines();
+    let first = Vec3::from(*lines[0].a);
+    assert!(close(first, Vec3::new(2.0, 0.0, 0.0)));
+    for line in &lines {
+        let a = Vec3::from(*line.a);
+        let b = Vec3::from(*line.b);
+        assert!((a.distance(b) - 2.0).abs() < 1e-5);
+        assert!((a.distance(Vec3::new(1.0, 1.0, 1.0)) - 3f32.sqrt()).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::matrix::fixed::SMatrix;
+use crate::matrix::scalar::{Rational, Scalar};
+use crate::matrix::{GenericMatrix, Matrix};
+
+fn hilbert(size: usize) -> GenericMatrix<Rational> {
+    let mut mat = GenericMatrix::new(size, size);
+    for i in 0..size {
+        for j in 0..size {
+            mat[(i, j)] = Rational::new(1, (i + j + 1) as i64);
+        }
+    }
+    return mat;
+}
+
+#[test]
+fn rational_arithmetic() {
+    let half = Rational::new(2, 4);
+    assert_eq!(half, Rational::new(1, 2));
+    assert_eq!(Rational::new(1, -2), -half);
+    assert_eq!(half + Rational::new(1, 3), Rational::new(5, 6));
+    assert_eq!(half * Rational::new(4, 3), Rational::new(2, 3));
+    assert_eq!(half / Rational::new(1, 4), Rational::from_integer(2));
+    assert!(Rational::new(1, 3) < half);
+    assert_eq!(
+        format!("{} {}", Rational::new(3, 6), Rational::new(4, 2)),
+        "1/2 2"
+    );
+}
+
+#[test]
+fn rational_overflow_panics() {
+    let overflows = |f: fn() -> Rational| {
+        let panic = std::panic::catch_unwind(f).unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+        assert!(message.starts_with("Rational overflow"), "{}", message);
+    };
+
+    overflows(|| Rational::from_integer(i64::MAX) * Rational::from_integer(2));
+    overflows(|| -Rational::from_integer(i64::MIN));
+    overflows(|| Rational::from_integer(i64::MIN).abs());
+    overflows(|| Rational::zero() - Rational::from_integer(i64::MIN));
+}
+
+#[test]
+fn integer_matrices_multiply_exactly() {
+    let a = GenericMatrix::<i64>::from_vec(vec![vec![1, 2], vec![3, 4]]).unwrap();
+    let b = GenericMatrix::<i64>::from_vec(vec![vec![5, 6], vec![7, 8]]).unwrap();
+
+    let expected = GenericMatrix::from_vec(vec![vec![19, 22], vec![43, 50]]).unwrap();
+    assert_eq!(&a * &b, expected);
+    assert_eq!(2 * &a - &a, a);
+
+    let fixed = SMatrix::<2, 2, i32>::from_array([[1, 2], [3, 4]]);
+    assert_eq!(fixed * SMatrix::identity(), fixed);
+}
+
+#[test]
+fn rational_inverse_is_exact() {
+    let h = hilbert(4);
+    let inv = h.inverse().unwrap();
+
+    assert_eq!(&h * &inv, GenericMatrix::identity(4));
+    assert_eq!(inv[(0, 0)], Rational::from_integer(16));
+    assert_eq!(inv[(3, 3)], Rational::from_integer(2800));
+    assert_eq!(h.determinant().unwrap(), Rational::new(1, 6048000));
+    assert_eq!(h.rank(), 4);
+}
+
+#[test]
+fn f64_beats_f32_on_ill_conditioned_input() {
+    let exact = hilbert(6);
+    let to_f64 = |x: Rational| x.numerator() as f64 / x.denominator() as f64;
+    let h64 = exact.map(to_f64);
+    let h32 = h64.map(|x| x as f32);
+
+    let error64 = max_identity_error(&(&h64 * &h64.inverse().unwrap()));
+    assert!(error64 < 1e-6);
+
+    // In f32 the same matrix is either flagged singular or inverted badly.
+    if let Ok(inv32) = h32.inverse() {
+        assert!(error64 < max_identity_error(&(&h32 * &inv32)));
+    }
+}
+
+#[test]
+fn f32_alias_keeps_existing_behaviour() {
+    let a: Matrix = Matrix::from_vec_i32(vec![vec![1, 2], vec![3, 4]]).unwrap();
+    assert_eq!(a, Matrix::from_array([[1.0, 2.0], [3.0, 4.0]]));
+    assert_eq!(a.map(|x| x as i32 * 2)[(1, 1)], 8);
+}
+
+fn max_identity_error<T: Scalar + Into<f64>>(m: &GenericMatrix<T>) -> f64 {
+    let mut max = 0.0_f64;
+    for i in 0..m.num_rows() {
+        for j in 0..m.num_cols() {
+            let expected = if i == j { 1.0 } else { 0.0 };
+            max = max.max((m[(i, j)].into() - expected).abs());
+        }
+    }
+    return max;
+}
@@ -0,0 +1,174 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::matrix::sparse::{CooMatrix, CsrMatrix};
+use crate::matrix::{GenericMatrix, Matrix, MatrixError};
+
+/// Graph Laplacian of an n x n grid plus `shift` on the diagonal, the
+/// symmetric positive definite system behind Laplacian mesh smoothing.
+fn grid_laplacian(n: usize, shift: f64) -> CsrMatrix<f64> {
+    let index = |i: usize, j: usize| i * n + j;
+    let mut coo = CooMatrix::new(n * n, n * n);
+
+    for i in 0..n {
+        for j in 0..n {
+            let mut neighbours = Vec::new();
+            if i + 1 < n {
+                neighbours.push(index(i + 1, j));
+            }
+            if j + 1 < n {
+                neighbours.push(index(i, j + 1));
+            }
+
+            coo.push(index(i, j), index(i, j), shift).unwrap();
+            for k in neighbours {
+                // Each edge contributes to both endpoints; duplicates are summed.
+                coo.push(index(i, j), index(i, j), 1.0).unwrap();
+                coo.push(k, k, 1.0).unwrap();
+                coo.push(index(i, j), k, -1.0).unwrap();
+                coo.push(k, index(i, j), -1.0).unwrap();
+            }
+        }
+    }
+
+    return coo.to_csr();
+}
+
+#[test]
+fn coo_sums_duplicates() {
+    let mut coo = CooMatrix::new(2, 3);
+    coo.push(1, 2, 4.0).unwrap();
+    coo.push(0, 0, 1.0).unwrap();
+    coo.push(1, 2, 0.5).unwrap();
+    assert_eq!(coo.nnz(), 3);
+
+    let csr = coo.to_csr();
+    assert_eq!(csr.nnz(), 2);
+    assert_eq!(csr.at(1, 2), Ok(4.5));
+    assert_eq!(csr.at(0, 1), Ok(0.0));
+    assert_eq!(
+        csr.to_dense(),
+        Matrix::from_array([[1.0, 0.0, 0.0], [0.0, 0.0, 4.5]])
+    );
+
+    assert_eq!(
+        coo.push(2, 0, 1.0),
+        Err(MatrixError::IndexOutOfBounds {
+            row: 2,
+            col: 0,
+            rows: 2,
+            cols: 3
+        })
+    );
+    assert!(csr.at(0, 3).is_err());
+}
+
+#[test]
+fn dense_round_trip() {
+    let dense = Matrix::from_array([[0.0, 2.0, 0.0], [0.0, 0.0, 0.0], [3.0, 0.0, -1.0]]);
+    let csr = CsrMatrix::from(&dense);
+
+    assert_eq!(csr.nnz(), 3);
+    assert_eq!(
+        csr.row(2).unwrap().collect::<Vec<_>>(),
+        vec![(0, 3.0), (2, -1.0)]
+    );
+    assert!(csr.row(3).is_err());
+    assert_eq!(Matrix::from(&csr), dense);
+    assert_eq!(
+        csr.transpose().to_dense(),
+        Matrix::from_array([[0.0, 0.0, 3.0], [2.0, 0.0, 0.0], [0.0, 0.0, -1.0]])
+    );
+    assert_eq!(
+        CsrMatrix::<f32>::identity(3).to_dense(),
+        Matrix::identity(3)
+    );
+}
+
+#[test]
+fn sparse_dense_multiply_matches_dense() {
+    let mut rng = StdRng::seed_from_u64(10);
+    let a = Matrix::new(6, 5).map(|_| {
+        if rng.gen_bool(0.3) {
+            rng.gen_range(-4..4) as f32
+        } else {
+            0.0
+        }
+    });
+    let b = Matrix::new(5, 4).map(|_| rng.gen_range(-4..4) as f32);
+    let csr = CsrMatrix::from_dense(&a);
+
+    // Small integers keep every sum exact, so the two orders agree exactly.
+    assert_eq!(csr.multiply_dense(&b).unwrap(), &a * &b);
+    assert_eq!(&csr * &b, &a * &b);
+
+    let x = [1.0, -2.0, 0.5, 3.0, 0.0];
+    let column = Matrix::from_row_major(5, 1, x.to_vec()).unwrap();
+    assert_eq!(
+        csr.multiply_vector(&x).unwrap(),
+        (&a * &column).as_slice().to_vec()
+    );
+
+    assert!(csr.multiply_dense(&Matrix::new(4, 4)).is_err());
+    assert!(csr.multiply_vector(&[1.0]).is_err());
+}
+
+#[test]
+fn conjugate_gradient_solves_laplacian() {
+    let n = 12;
+    let a = grid_laplacian(n, 0.1);
+    assert_eq!(a.nnz(), n * n + 4 * n * (n - 1));
+
+    let expected: Vec<f64> = (0..n * n).map(|i| (i as f64 * 0.37).sin()).collect();
+    let b = a.multiply_vector(&expected).unwrap();
+
+    let result = a.solve_cg(&b, 1e-12, 1000).unwrap();
+    assert!(result.iterations() <= n * n);
+    assert!(result.residual() <= 1e-12 * b.iter().map(|x| x * x).sum::<f64>().sqrt());
+    for (x, e) in result.solution().iter().zip(&expected) {
+        assert!((x - e).abs() < 1e-9, "{} vs {}", x, e);
+    }
+
+    // Agrees with the dense direct solver.
+    let rhs = GenericMatrix::from_row_major(n * n, 1, b.clone()).unwrap();
+    let direct = a.to_dense().solve(&rhs).unwrap();
+    for (x, d) in result.solution().iter().zip(direct.as_slice()) {
+        assert!((x - d).abs() < 1e-9, "{} vs {}", x, d);
+    }
+}
+
+#[test]
+fn conjugate_gradient_errors() {
+    let a = grid_laplacian(3, 0.0);
+
+    // A zero right-hand side converges immediately.
+    let zero = a.solve_cg(&[0.0; 9], 1e-10, 10).unwrap();
+    assert_eq!(zero.iterations(), 0);
+    assert_eq!(zero.solution(), &[0.0; 9]);
+
+    let b: Vec<f64> = (0..36).map(|i| i as f64 - 4.0).collect();
+    assert_eq!(
+        grid_laplacian(6, 0.01).solve_cg(&b, 1e-14, 2).map(|_| ()),
+        Err(MatrixError::NotConverged { iterations: 2 })
+    );
+    assert!(a.solve_cg(&b[..4], 1e-10, 10).is_err());
+
+    let mut negative = CooMatrix::new(2, 2);
+    negative.push(0, 0, -1.0).unwrap();
+    negative.push(1, 1, -1.0).unwrap();
+    assert_eq!(
+        negative
+            .to_csr()
+            .solve_cg(&[1.0, 1.0], 1e-10, 10)
+            .map(|_| ()),
+        Err(MatrixError::NotPositiveDefinite)
+    );
+
+    assert_eq!(
+        CooMatrix::<f64>::new(2, 3)
+            .to_csr()
+            .solve_cg(&[1.0, 1.0], 1e-10, 10)
+            .map(|_| ()),
+        Err(MatrixError::NotSquare { rows: 2, cols: 3 })
+    );
+}
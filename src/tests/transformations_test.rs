@@ -0,0 +1,258 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::matrix::compare::Tolerance;
+use crate::matrix::fixed::Matrix4;
+use crate::quaternion::Quaternion;
+use crate::solid::Point;
+use crate::tests::helpers::close;
+use crate::transformations::{
+    look_along, project_onto_plane, reflect, rotate, rotate_about_axis, rotate_between,
+    shadow_directional_light, shadow_point_light, shear, translate, RotationAxis,
+};
+use crate::vector::{Plane, Vec3};
+
+const TOLERANCE: Tolerance<f32> = Tolerance::Absolute(1e-5);
+
+fn random_vector(rng: &mut StdRng) -> Vec3 {
+    return Vec3::new(
+        rng.gen_range(-1.0..1.0),
+        rng.gen_range(-1.0..1.0),
+        rng.gen_range(-1.0..1.0),
+    );
+}
+
+fn transform(v: Vec3, m: &Matrix4) -> Vec3 {
+    return Vec3::from(Point::from(v).apply_matrix(m));
+}
+
+#[test]
+fn axis_rotation_matches_principal_axes() {
+    let theta = 1.1;
+    let (x, y, z) = (2.0, -1.0, 0.5);
+
+    let about_x = rotate_about_axis(Vec3::new(3.0, 0.0, 0.0), theta, x, y, z);
+    let about_z = rotate_about_axis(Vec3::new(0.0, 0.0, 1.0), theta, x, y, z);
+    assert!(about_x.approx_eq(&rotate(RotationAxis::X, theta, x, y, z), TOLERANCE));
+    assert!(about_z.approx_eq(&rotate(RotationAxis::Z, theta, x, y, z), TOLERANCE));
+
+    // `rotate` turns the other way about Y.
+    let about_y = rotate_about_axis(Vec3::new(0.0, 1.0, 0.0), -theta, x, y, z);
+    assert!(about_y.approx_eq(&rotate(RotationAxis::Y, theta, x, y, z), TOLERANCE));
+}
+
+#[test]
+fn axis_rotation_matches_quaternion() {
+    let mut rng = StdRng::seed_from_u64(19);
+
+    for _ in 0..20 {
+        let axis = random_vector(&mut rng);
+        let theta = rng.gen_range(-PI..PI);
+
+        let m = rotate_about_axis(axis, theta, 0.0, 0.0, 0.0);
+        let q = Quaternion::from_axis_angle(axis, theta);
+        assert!(m.approx_eq(&q.to_matrix(), TOLERANCE));
+        assert!(m.orthogonality_error() < 1e-5);
+    }
+}
+
+#[test]
+fn axis_rotation_keeps_the_axis_fixed() {
+    let axis = Vec3::new(1.0, 2.0, -1.0);
+    let pivot = Vec3::new(4.0, 0.0, -2.0);
+    let m = rotate_about_axis(axis, 0.8, pivot.x, pivot.y, pivot.z);
+
+    for t in [-2.0, 0.0, 3.5] {
+        let on_axis = pivot + axis * t;
+        assert!(close(transform(on_axis, &m), on_axis));
+    }
+
+    // Other points keep their distance from the axis.
+    let p = Vec3::new(0.0, 1.0, 1.0);
+    let q = transform(p, &m);
+    let distance = |v: Vec3| (v - pivot).cross(axis.normalize().unwrap()).length();
+    assert!((distance(p) - distance(q)).abs() < 1e-4);
+
+    assert_eq!(
+        rotate_about_axis(Vec3::zero(), 1.0, 1.0, 2.0, 3.0),
+        Matrix4::identity()
+    );
+}
+
+#[test]
+fn rotate_between_takes_one_direction_to_another() {
+    let mut rng = StdRng::seed_from_u64(119);
+
+    for _ in 0..20 {
+        let from = random_vector(&mut rng);
+        let to = random_vector(&mut rng);
+
+        let m = rotate_between(from, to);
+        let turned = transform(from, &m);
+        assert!(close(turned.normalize().unwrap(), to.normalize().unwrap()));
+        assert!((turned.length() - from.length()).abs() < 1e-5);
+
+        // The shortest rotation leaves the common normal in place.
+        let normal = from.cross(to);
+        assert!(close(transform(normal, &m), normal));
+    }
+}
+
+#[test]
+fn rotate_between_parallel_and_opposite_vectors() {
+    let v = Vec3::new(0.0, 2.0, 0.0);
+
+    let same = rotate_between(v, v * 3.0);
+    assert!(same.approx_eq(&Matrix4::identity(), TOLERANCE));
+
+    for from in [v, Vec3::new(1.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 1.0)] {
+        let opposite = rotate_between(from, -from);
+        assert!(close(transform(from, &opposite), -from));
+        assert!(opposite.orthogonality_error() < 1e-5);
+    }
+}
+
+#[test]
+fn look_along_points_forward_axis() {
+    let direction = Vec3::new(1.0, 1.0, 0.0);
+    let m = look_along(direction, Vec3::new(0.0, 0.0, 1.0));
+
+    let forward = transform(Vec3::new(0.0, 0.0, 1.0), &m);
+    let up = transform(Vec3::new(0.0, 1.0, 0.0), &m);
+    assert!(close(forward, direction.normalize().unwrap()));
+    assert!(close(up, Vec3::new(0.0, 0.0, 1.0)));
+    assert!(m.orthogonality_error() < 1e-5);
+
+    // A proper rotation, not a reflection.
+    let right = transform(Vec3::new(1.0, 0.0, 0.0), &m);
+    assert!(close(right.cross(up), forward));
+
+    // Looking straight along `up` still gives a rotation.
+    let m = look_along(Vec3::new(0.0, -3.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+    assert!(close(
+        transform(Vec3::new(0.0, 0.0, 1.0), &m),
+        Vec3::new(0.0, -1.0, 0.0)
+    ));
+    assert!(m.orthogonality_error() < 1e-5);
+
+    // The default orientation needs no rotation.
+    let m = look_along(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 1.0, 0.0));
+    assert!(m.approx_eq(&rotate(RotationAxis::Z, 0.0, 0.0, 0.0, 0.0), TOLERANCE));
+    let m = look_along(Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+    assert!(m.approx_eq(
+        &rotate_about_axis(Vec3::new(0.0, 1.0, 0.0), FRAC_PI_2, 0.0, 0.0, 0.0),
+        TOLERANCE
+    ));
+}
+
+#[test]
+fn shear_moves_each_coordinate_by_the_others() {
+    let m = shear(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+    let p = transform(Vec3::new(1.0, 10.0, 100.0), &m);
+
+    assert!(close(
+        p,
+        Vec3::new(1.0 + 10.0 + 200.0, 10.0 + 3.0 + 400.0, 100.0 + 5.0 + 60.0)
+    ));
+    assert_eq!(shear(0.0, 0.0, 0.0, 0.0, 0.0, 0.0), Matrix4::identity());
+
+    // Composes with the other builders in the usual row-vector order.
+    let moved = transform(
+        Vec3::new(0.0, 1.0, 0.0),
+        &(shear(2.0, 0.0, 0.0, 0.0, 0.0, 0.0) * translate(1.0, 0.0, 0.0)),
+    );
+    assert!(close(moved, Vec3::new(3.0, 1.0, 0.0)));
+}
+
+#[test]
+fn plane_normalises_coefficients() {
+    let plane = Plane::new(0.0, 0.0, 2.0, -4.0).unwrap();
+    assert_eq!(plane.normal, Vec3::new(0.0, 0.0, 1.0));
+    assert_eq!(plane.d, -2.0);
+    assert_eq!(plane.signed_distance(Vec3::new(5.0, 5.0, 3.0)), 1.0);
+
+    let through =
+        Plane::from_point_normal(Vec3::new(1.0, 1.0, 1.0), Vec3::new(1.0, 1.0, 0.0)).unwrap();
+    assert!(through.signed_distance(Vec3::new(1.0, 1.0, 7.0)).abs() < 1e-6);
+    assert!(Plane::new(0.0, 0.0, 0.0, 1.0).is_none());
+}
+
+#[test]
+fn reflection_mirrors_across_the_plane() {
+    let plane = Plane::new(1.0, 2.0, -2.0, 3.0).unwrap();
+    let m = reflect(&plane);
+
+    let mut rng = StdRng::seed_from_u64(20);
+    for _ in 0..10 {
+        let p = random_vector(&mut rng) * 5.0;
+        let q = transform(p, &m);
+
+        assert!((plane.signed_distance(q) + plane.signed_distance(p)).abs() < 1e-4);
+        assert!(close(transform(q, &m), p));
+        // The segment between a point and its image is along the normal.
+        assert!((q - p).cross(plane.normal).length() < 1e-4);
+    }
+
+    let det = m.to_matrix().determinant().unwrap();
+    assert!((det + 1.0).abs() < 1e-5);
+
+    // Across z = 0 only z changes sign.
+    let m = reflect(&Plane::new(0.0, 0.0, 1.0, 0.0).unwrap());
+    assert!(close(
+        transform(Vec3::new(1.0, 2.0, 3.0), &m),
+        Vec3::new(1.0, 2.0, -3.0)
+    ));
+}
+
+#[test]
+fn orthogonal_projection_lands_on_the_plane() {
+    let plane = Plane::new(0.0, 1.0, 1.0, -2.0).unwrap();
+    let m = project_onto_plane(&plane);
+
+    let p = Vec3::new(3.0, 4.0, -1.0);
+    let q = transform(p, &m);
+    assert!(plane.signed_distance(q).abs() < 1e-5);
+    assert!((q - p).cross(plane.normal).length() < 1e-5);
+    assert!(close(transform(q, &m), q));
+}
+
+#[test]
+fn point_light_shadow_follows_rays_from_the_light() {
+    let ground = Plane::new(0.0, 1.0, 0.0, 0.0).unwrap();
+    let light = Vec3::new(0.0, 10.0, 0.0);
+    let m = shadow_point_light(&ground, light);
+
+    // Half way down, the shadow is twice as far out.
+    let shadow = transform(Vec3::new(1.0, 5.0, 2.0), &m);
+    assert!(close(shadow, Vec3::new(2.0, 0.0, 4.0)));
+
+    let plane = Plane::new(1.0, -1.0, 2.0, 4.0).unwrap();
+    let light = Vec3::new(3.0, 2.0, 5.0);
+    let m = shadow_point_light(&plane, light);
+    let p = Vec3::new(1.0, 1.0, 1.0);
+    let q = transform(p, &m);
+
+    assert!(plane.signed_distance(q).abs() < 1e-4);
+    assert!((q - light).cross(p - light).length() < 1e-3);
+
+    // Points already on the plane stay put.
+    let on_plane = q;
+    assert!(close(transform(on_plane, &m), on_plane));
+}
+
+#[test]
+fn directional_shadow_projects_along_the_light() {
+    let ground = Plane::new(0.0, 1.0, 0.0, 1.0).unwrap();
+    let direction = Vec3::new(1.0, -1.0, 0.0);
+    let m = shadow_directional_light(&ground, direction);
+
+    let p = Vec3::new(0.0, 3.0, 2.0);
+    let q = transform(p, &m);
+    assert!(close(q, Vec3::new(4.0, -1.0, 2.0)));
+
+    // The scale of the direction does not matter.
+    let scaled = shadow_directional_light(&ground, direction * 7.0);
+    assert!(close(transform(p, &scaled), q));
+}
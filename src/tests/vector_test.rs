@@ -0,0 +1,140 @@
+use crate::drawline::{get_cos_x, get_cos_y, get_sin_x, get_sin_y};
+use crate::matrix::fixed::{Matrix4, SMatrix};
+use crate::matrix::{Matrix, MatrixError};
+use crate::solid::Point;
+use crate::transformations::{rotate, scale, translate, RotationAxis};
+use crate::vector::{Vec3, Vec4};
+
+#[test]
+fn vec3_arithmetic() {
+    let a = Vec3::new(1.0, 2.0, 3.0);
+    let b = Vec3::new(-4.0, 0.5, 2.0);
+
+    assert_eq!(a + b, Vec3::new(-3.0, 2.5, 5.0));
+    assert_eq!(a - b, Vec3::new(5.0, 1.5, 1.0));
+    assert_eq!(-a, Vec3::new(-1.0, -2.0, -3.0));
+    assert_eq!(a * 2.0, 2.0 * a);
+    assert_eq!(a / 2.0, Vec3::new(0.5, 1.0, 1.5));
+
+    let mut c = a;
+    c += b;
+    c -= a;
+    c *= 4.0;
+    c /= 2.0;
+    assert_eq!(c, b * 2.0);
+
+    c[1] = 7.0;
+    assert_eq!((c[0], c[1], c[2]), (-8.0, 7.0, 4.0));
+}
+
+#[test]
+fn vec3_geometry() {
+    let x = Vec3::new(1.0, 0.0, 0.0);
+    let y = Vec3::new(0.0, 1.0, 0.0);
+
+    assert_eq!(x.cross(y), Vec3::new(0.0, 0.0, 1.0));
+    assert_eq!(y.cross(x), Vec3::new(0.0, 0.0, -1.0));
+    assert_eq!(x.dot(y), 0.0);
+
+    let a = Vec3::new(3.0, -4.0, 12.0);
+    assert_eq!(a.length(), 13.0);
+    assert_eq!(a.length_squared(), 169.0);
+    assert_eq!(a.distance(Vec3::zero()), 13.0);
+    assert!((a.normalize().unwrap().length() - 1.0).abs() < 1e-6);
+    assert_eq!(a.cross(a), Vec3::zero());
+    assert_eq!(a.cross(x).dot(a), 0.0);
+    assert_eq!(Vec3::zero().normalize(), None);
+
+    assert_eq!(x.lerp(y, 0.0), x);
+    assert_eq!(x.lerp(y, 1.0), y);
+    assert_eq!(x.lerp(y, 0.25), Vec3::new(0.75, 0.25, 0.0));
+}
+
+#[test]
+fn vec4_homogeneous() {
+    let p = Vec3::new(2.0, 4.0, 6.0).extend(2.0);
+    assert_eq!(p, Vec4::new(2.0, 4.0, 6.0, 2.0));
+    assert_eq!(p.truncate(), Vec3::new(2.0, 4.0, 6.0));
+    assert_eq!(p.project(), Some(Vec3::new(1.0, 2.0, 3.0)));
+    assert_eq!(Vec4::new(1.0, 0.0, 0.0, 0.0).project(), None);
+
+    assert_eq!(p.dot(p), 60.0);
+    assert_eq!(Vec4::new(0.0, 3.0, 0.0, 4.0).length(), 5.0);
+    assert_eq!(Vec4::zero().normalize(), None);
+    assert_eq!(Vec4::zero().lerp(p, 0.5) + p * 0.5 - p, Vec4::zero());
+    assert_eq!(p[3], 2.0);
+}
+
+#[test]
+fn vec4_times_matrix_matches_row_matrix() {
+    let m = rotate(RotationAxis::Z, 0.4, 1.0, 2.0, 3.0) * scale(2.0, 3.0, 4.0, 0.0, 1.0, 0.0);
+    let v = Vec4::new(1.5, -2.0, 0.25, 1.0);
+
+    let row = SMatrix::<1, 4>::from(v) * m;
+    assert_eq!(v * m, Vec4::from(row));
+
+    let mut w = v;
+    w *= translate(1.0, 1.0, 1.0);
+    assert_eq!(w, Vec4::new(2.5, -1.0, 1.25, 1.0));
+
+    // Directions ignore translation.
+    let direction = Vec4::new(1.0, 0.0, 0.0, 0.0);
+    assert_eq!(direction * translate(5.0, 6.0, 7.0), direction);
+    assert_eq!(
+        Vec4::from([1.0, 2.0, 3.0, 4.0]) * Matrix4::identity(),
+        Vec4::new(1.0, 2.0, 3.0, 4.0)
+    );
+}
+
+#[test]
+fn conversions() {
+    let p = Point::from_f32(1.0, 2.0, 3.0);
+    let v = Vec3::from(&p);
+    assert_eq!(v, Vec3::new(1.0, 2.0, 3.0));
+    let back = Point::from(v);
+    assert_eq!((back.x, back.y, back.z), (1.0, 2.0, 3.0));
+
+    assert_eq!(<[f32; 3]>::from(v), [1.0, 2.0, 3.0]);
+    assert_eq!(Matrix::from(v), Matrix::from_array([[1.0, 2.0, 3.0]]));
+
+    let m = Matrix::from_array([[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]]);
+    assert_eq!(
+        Vec4::try_from(m.row(1).unwrap()),
+        Ok(Vec4::new(5.0, 6.0, 7.0, 8.0))
+    );
+    assert_eq!(
+        Vec3::try_from(m.row(0).unwrap()),
+        Err(MatrixError::DataLength {
+            expected: 3,
+            found: 4
+        })
+    );
+    assert_eq!(Vec4::new(1.0, 2.0, 3.0, 4.0).to_string(), "(1, 2, 3, 4)");
+}
+
+#[test]
+#[should_panic(expected = "index 3 out of bounds for a 3-vector")]
+fn vec3_index_out_of_bounds_panics() {
+    let _ = Vec3::zero()[3];
+}
+
+#[test]
+fn view_angles_from_vectors() {
+    let (x, y, z) = (3.0, 4.0, 12.0);
+    assert_eq!(get_sin_x(x, y, z), Some(12.0 / 13.0));
+    assert_eq!(get_cos_x(x, y, z), Some(5.0 / 13.0));
+    assert_eq!(get_sin_y(x, y), Some(0.6));
+    assert_eq!(get_cos_y(x, y), Some(0.8));
+
+    // Only a zero vector has no direction; opposite components cancelling
+    // in a sum do not count.
+    assert_eq!(get_sin_x(0.0, 0.0, 0.0), None);
+    assert_eq!(get_sin_y(0.0, 0.0), None);
+    assert_eq!(get_sin_y(1.0, -1.0), Some(1.0 / 2f32.sqrt()));
+    assert_eq!(get_cos_y(1.0, -1.0), Some(-1.0 / 2f32.sqrt()));
+
+    // The original code returned `None` whenever `x + y + z == 0`.
+    assert_eq!(get_sin_x(1.0, -1.0, 0.0), Some(0.0));
+    assert_eq!(get_cos_x(1.0, -1.0, 0.0), Some(1.0));
+    assert_eq!(get_sin_x(2.0, -3.0, 1.0), Some(1.0 / 14f32.sqrt()));
+}
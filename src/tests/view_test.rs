@@ -0,0 +1,178 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::matrix::scalar::Rational;
+use crate::matrix::{GenericMatrix, Matrix, MatrixError};
+
+fn counting(rows: usize, cols: usize) -> Matrix {
+    let mut m = Matrix::new(rows, cols);
+    for i in 0..rows {
+        for j in 0..cols {
+            m[(i, j)] = (10 * i + j) as f32;
+        }
+    }
+    return m;
+}
+
+#[test]
+fn transpose_copy_and_view() {
+    let m = counting(2, 3);
+    let t = m.transpose();
+
+    assert_eq!(
+        t,
+        Matrix::from_array([[0.0, 10.0], [1.0, 11.0], [2.0, 12.0]])
+    );
+    assert_eq!(m.transpose_view().to_matrix(), t);
+    assert_eq!(m.transpose_view()[(2, 1)], 12.0);
+    assert_eq!(m.transpose_view().transpose().to_matrix(), m);
+    assert_eq!(t.transpose(), m);
+    assert_eq!(Matrix::new(0, 3).transpose(), Matrix::new(3, 0));
+}
+
+#[test]
+fn submatrix_views() {
+    let m = counting(4, 5);
+
+    let block = m.submatrix(1, 2, 2, 3).unwrap();
+    assert_eq!((block.num_rows(), block.num_cols()), (2, 3));
+    assert_eq!(
+        block.to_matrix(),
+        Matrix::from_array([[12.0, 13.0, 14.0], [22.0, 23.0, 24.0]])
+    );
+
+    // Views compose: a block of the transposed block.
+    let inner = block.transpose().submatrix(1, 1, 2, 1).unwrap();
+    assert_eq!(inner.to_matrix(), Matrix::from_array([[23.0], [24.0]]));
+    assert_eq!(inner.at(1, 0), Ok(24.0));
+    assert!(inner.at(0, 1).is_err());
+
+    assert_eq!(
+        m.submatrix(4, 5, 0, 0).unwrap().to_matrix(),
+        Matrix::new(0, 0)
+    );
+    assert_eq!(
+        m.submatrix(3, 0, 2, 1).map(|v| v.to_matrix()),
+        Err(MatrixError::IndexOutOfBounds {
+            row: 4,
+            col: 0,
+            rows: 4,
+            cols: 5
+        })
+    );
+    // Sizes that overflow `row + rows` are out of bounds, not wrapped.
+    assert!(m.submatrix(1, 0, usize::MAX, 1).is_err());
+    assert!(m.submatrix(0, 2, 1, usize::MAX - 1).is_err());
+}
+
+#[test]
+#[should_panic(expected = "index (2, 0) out of bounds for 2x3 matrix")]
+fn view_index_out_of_bounds_panics() {
+    let m = counting(4, 4);
+    let _ = m.submatrix(0, 0, 2, 3).unwrap()[(2, 0)];
+}
+
+#[test]
+fn row_and_column_iterators() {
+    let m = counting(3, 2);
+
+    assert_eq!(m.row(1).unwrap(), &[10.0, 11.0]);
+    assert_eq!(
+        m.column(1).unwrap().collect::<Vec<_>>(),
+        vec![1.0, 11.0, 21.0]
+    );
+    assert_eq!(m.column(0).unwrap().len(), 3);
+    assert!(m.row(3).is_err());
+    assert!(m.column(2).is_err());
+
+    let rows: Vec<Vec<f32>> = m.rows().map(|r| r.to_vec()).collect();
+    assert_eq!(
+        rows,
+        vec![vec![0.0, 1.0], vec![10.0, 11.0], vec![20.0, 21.0]]
+    );
+
+    let columns: Vec<Vec<f32>> = m.columns().map(|c| c.collect()).collect();
+    let view_rows: Vec<Vec<f32>> = m.transpose_view().rows().map(|r| r.collect()).collect();
+    assert_eq!(columns, view_rows);
+
+    let view = m.submatrix(1, 0, 2, 2).unwrap();
+    assert_eq!(view.row(1).unwrap().collect::<Vec<_>>(), vec![20.0, 21.0]);
+    assert_eq!(
+        view.column(1).unwrap().collect::<Vec<_>>(),
+        vec![11.0, 21.0]
+    );
+    assert_eq!(view.columns().count(), 2);
+    assert!(view.row(2).is_err());
+}
+
+#[test]
+fn minors_and_cofactors() {
+    let m =
+        GenericMatrix::from_array([[1, 2, 3], [0, 4, 5], [1, 0, 6]]).map(Rational::from_integer);
+
+    assert_eq!(
+        m.minor_matrix(1, 0).unwrap(),
+        GenericMatrix::from_array([[2, 3], [0, 6]]).map(Rational::from_integer)
+    );
+    assert_eq!(m.minor(1, 0), Ok(Rational::from_integer(12)));
+    assert_eq!(m.cofactor(1, 0), Ok(Rational::from_integer(-12)));
+
+    // Laplace expansion along the first row gives the determinant.
+    let expansion: Rational = (0..3)
+        .map(|j| m.at(0, j).unwrap() * m.cofactor(0, j).unwrap())
+        .sum();
+    assert_eq!(expansion, m.determinant().unwrap());
+
+    // A * adj(A) = det(A) * I, exactly.
+    let det = m.determinant().unwrap();
+    assert_eq!(
+        &m * &m.adjugate().unwrap(),
+        GenericMatrix::identity(3) * det
+    );
+    assert_eq!(m.adjugate().unwrap() / det, m.inverse().unwrap());
+
+    assert_eq!(Matrix::identity(1).minor(0, 0), Ok(1.0));
+    assert!(Matrix::new(2, 3).minor(0, 0).is_err());
+    assert!(m.minor_matrix(3, 0).is_err());
+}
+
+#[test]
+fn stacking() {
+    let a = counting(2, 2);
+    let b = Matrix::from_array([[7.0], [8.0]]);
+
+    assert_eq!(
+        a.hstack(&b).unwrap(),
+        Matrix::from_array([[0.0, 1.0, 7.0], [10.0, 11.0, 8.0]])
+    );
+    assert_eq!(
+        a.vstack(&b.transpose()).unwrap(),
+        Matrix::from_array([[0.0, 1.0], [10.0, 11.0], [7.0, 8.0]])
+    );
+    assert_eq!(
+        a.hstack(&b.transpose()),
+        Err(MatrixError::DimensionMismatch {
+            left: (2, 2),
+            right: (1, 2)
+        })
+    );
+    assert!(a.vstack(&b).is_err());
+
+    // Splitting a stacked matrix with views gives the parts back.
+    let stacked = a.hstack(&b).unwrap();
+    assert_eq!(stacked.submatrix(0, 2, 2, 1).unwrap().to_matrix(), b);
+}
+
+#[test]
+fn all_multiplies_agree_bit_for_bit() {
+    let mut rng = StdRng::seed_from_u64(14);
+
+    for (rows, inner, cols) in [(1, 1, 1), (3, 5, 2), (17, 9, 33), (0, 3, 2), (2, 0, 3)] {
+        let a = Matrix::new(rows, inner).map(|_| rng.gen::<f32>() * 100.0 - 50.0);
+        let b = Matrix::new(inner, cols).map(|_| rng.gen::<f32>() * 100.0 - 50.0);
+
+        let expected = a.matrix_multiply_blocked(&b).unwrap();
+        assert_eq!(a.matrix_multiply(&b).unwrap(), expected);
+        assert_eq!(a.matrix_multiply2(&b).unwrap(), expected);
+    }
+}
@@ -1,44 +1,49 @@
-use std::f32;
+pub mod builder;
+pub mod decompose;
+pub mod stack;
 
-use crate::matrix::Matrix;
+use crate::matrix::fixed::Matrix4;
+use crate::matrix::MatrixError;
+use crate::vector::{Plane, Vec3, Vec4};
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RotationAxis {
     X,
     Y,
     Z,
 }
 
-pub fn translate(x: f32, y: f32, z: f32) -> Matrix {
-    let t = Matrix::from_vec(vec![
-        vec![1.0, 0.0, 0.0, 0.0],
-        vec![0.0, 1.0, 0.0, 0.0],
-        vec![0.0, 0.0, 1.0, 0.0],
-        vec![x, y, z, 1.0],
+pub fn translate(x: f32, y: f32, z: f32) -> Matrix4 {
+    let t = Matrix4::from_array([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [x, y, z, 1.0],
     ]);
 
     return t;
 }
 
-pub fn basic_scale(x: f32, y: f32, z: f32) -> Matrix {
-    let t = Matrix::from_vec(vec![
-        vec![x, 0.0, 0.0, 0.0],
-        vec![0.0, y, 0.0, 0.0],
-        vec![0.0, 0.0, z, 0.0],
-        vec![0.0, 0.0, 0.0, 1.0],
+pub fn basic_scale(x: f32, y: f32, z: f32) -> Matrix4 {
+    let t = Matrix4::from_array([
+        [x, 0.0, 0.0, 0.0],
+        [0.0, y, 0.0, 0.0],
+        [0.0, 0.0, z, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
     ]);
 
     return t;
 }
 
-pub fn scale(x: f32, y: f32, z: f32, cx: f32, cy: f32, cz: f32) -> Matrix {
+pub fn scale(x: f32, y: f32, z: f32, cx: f32, cy: f32, cz: f32) -> Matrix4 {
     let a = translate(-cx, -cy, -cz);
     let b = basic_scale(x, y, z);
     let c = translate(cx, cy, cz);
 
-    return a.matrix_multiply(&b).unwrap().matrix_multiply(&c).unwrap();
+    return a * b * c;
 }
 
-pub fn rotate(axis: RotationAxis, theta: f32, x: f32, y: f32, z: f32) -> Matrix {
+pub fn rotate(axis: RotationAxis, theta: f32, x: f32, y: f32, z: f32) -> Matrix4 {
     let cos = f32::cos(theta);
     let sin = f32::sin(theta);
 
@@ -51,40 +56,228 @@ pub fn rotate(axis: RotationAxis, theta: f32, x: f32, y: f32, z: f32) -> Matrix
         RotationAxis::Z => get_rotation_matrix_z(sin, cos),
     };
 
-    return a.matrix_multiply(&r).unwrap().matrix_multiply(&b).unwrap();
+    return a * r * b;
 }
 
+/// Rotation by `theta` about the line through `(x, y, z)` with direction
+/// `axis`, using Rodrigues' formula. Positive angles follow the right-hand
+/// rule, as `rotate` does about X and Z. A zero axis gives the identity.
+pub fn rotate_about_axis(axis: Vec3, theta: f32, x: f32, y: f32, z: f32) -> Matrix4 {
+    let k = match axis.normalize() {
+        Some(k) => k,
+        None => return Matrix4::identity(),
+    };
+    let (sin, cos) = theta.sin_cos();
+    let t = 1.0 - cos;
+
+    // Transpose of `cos I + sin [k]x + (1 - cos) k k^T` for row vectors.
+    let r = Matrix4::from_array([
+        [
+            cos + t * k.x * k.x,
+            t * k.x * k.y + sin * k.z,
+            t * k.x * k.z - sin * k.y,
+            0.0,
+        ],
+        [
+            t * k.y * k.x - sin * k.z,
+            cos + t * k.y * k.y,
+            t * k.y * k.z + sin * k.x,
+            0.0,
+        ],
+        [
+            t * k.z * k.x + sin * k.y,
+            t * k.z * k.y - sin * k.x,
+            cos + t * k.z * k.z,
+            0.0,
+        ],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+
+    let a = translate(-x, -y, -z);
+    let b = translate(x, y, z);
+
+    return a * r * b;
+}
+
+/// Shortest rotation about the origin that turns the direction of `from`
+/// onto the direction of `to`. Opposite vectors are turned half way round an
+/// arbitrary perpendicular axis; a zero vector gives the identity.
+#[allow(dead_code)]
+pub fn rotate_between(from: Vec3, to: Vec3) -> Matrix4 {
+    let (a, b) = match (from.normalize(), to.normalize()) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return Matrix4::identity(),
+    };
+
+    let axis = a.cross(b);
+    let sin = axis.length();
+    let cos = a.dot(b);
+
+    if sin <= f32::EPSILON && cos < 0.0 {
+        return rotate_about_axis(perpendicular(a), std::f32::consts::PI, 0.0, 0.0, 0.0);
+    }
+
+    return rotate_about_axis(axis, sin.atan2(cos), 0.0, 0.0, 0.0);
+}
+
+/// Rotation about the origin that points an object's +Z axis along
+/// `direction` and keeps its +Y axis as close to `up` as possible. When `up`
+/// is parallel to `direction` another up vector is chosen; a zero direction
+/// gives the identity.
 #[allow(dead_code)]
-fn get_rotation_matrix_x(sin: f32, cos: f32) -> Matrix {
-    let r = Matrix::from_vec(vec![
-        vec![1.0, 0.0, 0.0, 0.0],
-        vec![0.0, cos, sin, 0.0],
-        vec![0.0, -sin, cos, 0.0],
-        vec![0.0, 0.0, 0.0, 0.0],
+pub fn look_along(direction: Vec3, up: Vec3) -> Matrix4 {
+    let forward = match direction.normalize() {
+        Some(forward) => forward,
+        None => return Matrix4::identity(),
+    };
+    let right = up
+        .cross(forward)
+        .normalize()
+        .unwrap_or_else(|| perpendicular(forward));
+    let up = forward.cross(right);
+
+    // Row vectors: each row is where one local axis ends up.
+    return Matrix4::from_array([
+        [right.x, right.y, right.z, 0.0],
+        [up.x, up.y, up.z, 0.0],
+        [forward.x, forward.y, forward.z, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
     ]);
+}
 
-    return r;
+/// A unit vector perpendicular to the unit vector `v`.
+#[allow(dead_code)]
+fn perpendicular(v: Vec3) -> Vec3 {
+    let helper = if v.x.abs() < 0.9 {
+        Vec3::new(1.0, 0.0, 0.0)
+    } else {
+        Vec3::new(0.0, 1.0, 0.0)
+    };
+
+    return v
+        .cross(helper)
+        .normalize()
+        .expect("helper is not parallel to v");
+}
+
+/// Shear where each coordinate gains a multiple of the other two: `xy` is how
+/// far x moves per unit of y, `zx` how far z moves per unit of x, and so on.
+pub fn shear(xy: f32, xz: f32, yx: f32, yz: f32, zx: f32, zy: f32) -> Matrix4 {
+    let t = Matrix4::from_array([
+        [1.0, yx, zx, 0.0],
+        [xy, 1.0, zy, 0.0],
+        [xz, yz, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+
+    return t;
+}
+
+/// Mirror image across `plane`.
+pub fn reflect(plane: &Plane) -> Matrix4 {
+    return householder(plane, 2.0);
 }
 
+/// Orthogonal projection onto `plane`: every point moves along the normal to
+/// its nearest point on the plane.
 #[allow(dead_code)]
-fn get_rotation_matrix_y(sin: f32, cos: f32) -> Matrix {
-    let r = Matrix::from_vec(vec![
-        vec![cos, 0.0, sin, 0.0],
-        vec![0.0, 1.0, 0.0, 0.0],
-        vec![-sin, 0.0, cos, 0.0],
-        vec![0.0, 0.0, 0.0, 0.0],
+pub fn project_onto_plane(plane: &Plane) -> Matrix4 {
+    return householder(plane, 1.0);
+}
+
+/// Projects points onto `plane` along rays from a point light at `light`.
+/// The result has a non-trivial w, so apply it with `Point::apply_matrix`.
+/// Points level with the light, parallel to the plane, go to infinity, and a
+/// light on the plane gives a degenerate matrix.
+#[allow(dead_code)]
+pub fn shadow_point_light(plane: &Plane, light: Vec3) -> Matrix4 {
+    return shadow(plane, light.extend(1.0));
+}
+
+/// Projects points onto `plane` along the direction the light travels. A
+/// direction parallel to the plane gives a degenerate matrix.
+#[allow(dead_code)]
+pub fn shadow_directional_light(plane: &Plane, direction: Vec3) -> Matrix4 {
+    return shadow(plane, direction.extend(0.0));
+}
+
+/// `I - k n n^T` with the matching translation: `k = 1` projects onto the
+/// plane and `k = 2` reflects across it.
+fn householder(plane: &Plane, k: f32) -> Matrix4 {
+    let n = plane.normal;
+    let mut t = Matrix4::identity();
+
+    for i in 0..3 {
+        for j in 0..3 {
+            t[(i, j)] -= k * n[i] * n[j];
+        }
+        t[(3, i)] = -k * plane.d * n[i];
+    }
+
+    return t;
+}
+
+/// The classic shadow matrix `(P . L) I - L P^T`, transposed for row vectors.
+#[allow(dead_code)]
+fn shadow(plane: &Plane, light: Vec4) -> Matrix4 {
+    let p = plane.coefficients();
+    let dot = p.dot(light);
+    let mut t = Matrix4::new();
+
+    for i in 0..4 {
+        for j in 0..4 {
+            let diagonal = if i == j { dot } else { 0.0 };
+            t[(i, j)] = diagonal - p[i] * light[j];
+        }
+    }
+
+    return t;
+}
+
+/// Blends two transforms with constant angular and linear velocity, so that
+/// blending rigid motions stays rigid. `t = 0` gives `from` and `t = 1` gives
+/// `to`. Fails if `from` is singular or the motion between the two has no
+/// principal logarithm, such as an exact half turn.
+#[allow(dead_code)]
+pub fn interpolate(from: &Matrix4, to: &Matrix4, t: f32) -> Result<Matrix4, MatrixError> {
+    let a = from.to_matrix().map(|x| x as f64);
+    let b = to.to_matrix().map(|x| x as f64);
+
+    // Row vectors apply left to right, so `to = from * delta`.
+    let delta = &a.inverse()? * &b;
+    let step = (delta.log()? * t as f64).exp()?;
+
+    return Matrix4::try_from((&a * &step).map(|x| x as f32));
+}
+
+fn get_rotation_matrix_x(sin: f32, cos: f32) -> Matrix4 {
+    let r = Matrix4::from_array([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, cos, sin, 0.0],
+        [0.0, -sin, cos, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
     ]);
 
     return r;
 }
 
-#[allow(dead_code)]
-fn get_rotation_matrix_z(sin: f32, cos: f32) -> Matrix {
-    let r = Matrix::from_vec(vec![
-        vec![cos, sin, 0.0, 0.0],
-        vec![-sin, cos, 0.0, 0.0],
-        vec![0.0, 0.0, 1.0, 0.0],
-        vec![0.0, 0.0, 0.0, 1.0],
+fn get_rotation_matrix_y(sin: f32, cos: f32) -> Matrix4 {
+    let r = Matrix4::from_array([
+        [cos, 0.0, sin, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [-sin, 0.0, cos, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+
+    return r;
+}
+
+fn get_rotation_matrix_z(sin: f32, cos: f32) -> Matrix4 {
+    let r = Matrix4::from_array([
+        [cos, sin, 0.0, 0.0],
+        [-sin, cos, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
     ]);
 
     return r;
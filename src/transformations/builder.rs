@@ -0,0 +1,166 @@
+use crate::matrix::fixed::Matrix4;
+use crate::quaternion::Quaternion;
+use crate::solid::Point;
+use crate::transformations::{
+    reflect, rotate, rotate_about_axis, scale, shear, translate, RotationAxis,
+};
+use crate::vector::{Plane, Vec3};
+
+/// One recorded step of a `Transform`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operation {
+    Translate(Vec3),
+    Scale {
+        factors: Vec3,
+        center: Vec3,
+    },
+    Rotate {
+        axis: RotationAxis,
+        theta: f32,
+        center: Vec3,
+    },
+    RotateAbout {
+        axis: Vec3,
+        theta: f32,
+        center: Vec3,
+    },
+    Quaternion {
+        rotation: Quaternion,
+        center: Vec3,
+    },
+    Shear([f32; 6]),
+    Reflect(Plane),
+    Matrix(Matrix4),
+}
+
+/// Records transformations and composes them into one matrix.
+///
+/// Steps apply in the order they are written: the first call moves the
+/// object first. The matrices act on row vectors (`p * M`, translation in
+/// the bottom row), so `build` multiplies them left to right in that same
+/// order, matching `a * b` for the free functions.
+///
+/// ```text
+/// let m = Transform::new()
+///     .scale(2.0, 2.0, 2.0)
+///     .rotate_about(Vec3::new(0.0, 1.0, 0.0), angle, Vec3::zero())
+///     .translate(5.0, 0.0, 0.0)
+///     .build();
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Transform {
+    operations: Vec<Operation>,
+}
+
+impl Operation {
+    pub fn to_matrix(self) -> Matrix4 {
+        return match self {
+            Operation::Translate(v) => translate(v.x, v.y, v.z),
+            Operation::Scale { factors, center } => scale(
+                factors.x, factors.y, factors.z, center.x, center.y, center.z,
+            ),
+            Operation::Rotate {
+                axis,
+                theta,
+                center,
+            } => rotate(axis, theta, center.x, center.y, center.z),
+            Operation::RotateAbout {
+                axis,
+                theta,
+                center,
+            } => rotate_about_axis(axis, theta, center.x, center.y, center.z),
+            Operation::Quaternion { rotation, center } => rotation.to_matrix_about(center),
+            Operation::Shear([xy, xz, yx, yz, zx, zy]) => shear(xy, xz, yx, yz, zx, zy),
+            Operation::Reflect(plane) => reflect(&plane),
+            Operation::Matrix(m) => m,
+        };
+    }
+}
+
+impl Transform {
+    pub fn new() -> Self {
+        Self {
+            operations: Vec::new(),
+        }
+    }
+
+    pub fn translate(self, x: f32, y: f32, z: f32) -> Self {
+        return self.then(Operation::Translate(Vec3::new(x, y, z)));
+    }
+
+    /// Scales about the origin.
+    pub fn scale(self, x: f32, y: f32, z: f32) -> Self {
+        return self.scale_about(x, y, z, Vec3::zero());
+    }
+
+    pub fn scale_about(self, x: f32, y: f32, z: f32, center: Vec3) -> Self {
+        return self.then(Operation::Scale {
+            factors: Vec3::new(x, y, z),
+            center,
+        });
+    }
+
+    /// Rotates about a principal axis through the origin, as
+    /// `transformations::rotate` does.
+    pub fn rotate(self, axis: RotationAxis, theta: f32) -> Self {
+        return self.then(Operation::Rotate {
+            axis,
+            theta,
+            center: Vec3::zero(),
+        });
+    }
+
+    /// Rotates by `theta` about the line through `center` along `axis`.
+    pub fn rotate_about(self, axis: Vec3, theta: f32, center: Vec3) -> Self {
+        return self.then(Operation::RotateAbout {
+            axis,
+            theta,
+            center,
+        });
+    }
+
+    pub fn rotate_by(self, rotation: Quaternion, center: Vec3) -> Self {
+        return self.then(Operation::Quaternion { rotation, center });
+    }
+
+    /// See `transformations::shear` for the meaning of each factor.
+    pub fn shear(self, xy: f32, xz: f32, yx: f32, yz: f32, zx: f32, zy: f32) -> Self {
+        return self.then(Operation::Shear([xy, xz, yx, yz, zx, zy]));
+    }
+
+    pub fn reflect(self, plane: Plane) -> Self {
+        return self.then(Operation::Reflect(plane));
+    }
+
+    /// Appends an arbitrary matrix.
+    pub fn matrix(self, matrix: Matrix4) -> Self {
+        return self.then(Operation::Matrix(matrix));
+    }
+
+    pub fn then(mut self, operation: Operation) -> Self {
+        self.operations.push(operation);
+        return self;
+    }
+
+    pub fn operations(&self) -> &[Operation] {
+        return &self.operations;
+    }
+
+    /// The product of every step, first step leftmost.
+    pub fn build(&self) -> Matrix4 {
+        return self
+            .operations
+            .iter()
+            .fold(Matrix4::identity(), |m, op| m * op.to_matrix());
+    }
+
+    pub fn apply(&self, point: &Point) -> Point {
+        return point.apply_matrix(&self.build());
+    }
+}
+
+impl From<&Transform> for Matrix4 {
+    fn from(transform: &Transform) -> Self {
+        return transform.build();
+    }
+}
@@ -0,0 +1,108 @@
+use crate::matrix::fixed::Matrix4;
+use crate::matrix::MatrixError;
+use crate::quaternion::{EulerOrder, Quaternion};
+use crate::transformations::{basic_scale, shear, translate};
+use crate::vector::Vec3;
+
+/// The pieces of an affine transform, applied to row vectors in the order
+/// scale, shear, rotation, translation:
+///
+/// `M = basic_scale(scale) * shear(xy, xz, 0, yz, 0, 0) * rotation * translate`
+///
+/// A reflection is reported as a negative `scale.x` with `reflection` set,
+/// which keeps `rotation` a proper rotation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AffineParts {
+    pub translation: Vec3,
+    pub rotation: Quaternion,
+    pub scale: Vec3,
+    /// `[xy, xz, yz]`: how far x moves per unit of y and of z, and how far
+    /// y moves per unit of z, as in `transformations::shear`.
+    pub shear: [f32; 3],
+    pub reflection: bool,
+}
+
+impl AffineParts {
+    pub fn euler(self, order: EulerOrder) -> [f32; 3] {
+        return self.rotation.to_euler(order);
+    }
+
+    /// Rebuilds the transform.
+    pub fn to_matrix(self) -> Matrix4 {
+        let [xy, xz, yz] = self.shear;
+        let t = self.translation;
+
+        return basic_scale(self.scale.x, self.scale.y, self.scale.z)
+            * shear(xy, xz, 0.0, yz, 0.0, 0.0)
+            * self.rotation.to_matrix()
+            * translate(t.x, t.y, t.z);
+    }
+}
+
+impl Matrix4 {
+    /// Splits an affine transform into `AffineParts`. A non-zero perspective
+    /// column gives `NotAffine`; a matrix that flattens space onto a plane,
+    /// line or point gives `Singular`. A bottom-right element other than one
+    /// is divided out first.
+    pub fn decompose_affine(&self) -> Result<AffineParts, MatrixError> {
+        let w = self[(3, 3)];
+        if (0..3).any(|r| self[(r, 3)] != 0.0) || w == 0.0 {
+            return Err(MatrixError::NotAffine);
+        }
+
+        let row = |r: usize| Vec3::new(self[(r, 0)], self[(r, 1)], self[(r, 2)]) / w;
+        let translation = row(3);
+        let mut rows = [row(0), row(1), row(2)];
+
+        let reflection = rows[0].cross(rows[1]).dot(rows[2]) < 0.0;
+        if reflection {
+            rows[0] = -rows[0];
+        }
+
+        // Gram-Schmidt on the rows, which are the images of the local axes.
+        let size = rows.iter().map(|r| r.length()).fold(0.0, f32::max);
+        let tolerance = size * 1e-6;
+
+        let sx = rows[0].length();
+        if sx <= tolerance {
+            return Err(MatrixError::Singular);
+        }
+        let x = rows[0] / sx;
+
+        let mut y = rows[1];
+        let xy = x.dot(y);
+        y -= x * xy;
+        let sy = y.length();
+        if sy <= tolerance {
+            return Err(MatrixError::Singular);
+        }
+        let y = y / sy;
+
+        let mut z = rows[2];
+        let xz = x.dot(z);
+        z -= x * xz;
+        let yz = y.dot(z);
+        z -= y * yz;
+        let sz = z.length();
+        if sz <= tolerance {
+            return Err(MatrixError::Singular);
+        }
+        let z = z / sz;
+
+        let rotation = Quaternion::from_matrix(&Matrix4::from_array([
+            [x.x, x.y, x.z, 0.0],
+            [y.x, y.y, y.z, 0.0],
+            [z.x, z.y, z.z, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]))?;
+        let sign = if reflection { -1.0 } else { 1.0 };
+
+        return Ok(AffineParts {
+            translation,
+            rotation,
+            scale: Vec3::new(sign * sx, sy, sz),
+            shear: [xy / sy, xz / sz, yz / sz],
+            reflection,
+        });
+    }
+}
@@ -0,0 +1,76 @@
+use crate::matrix::fixed::Matrix4;
+use crate::solid::Point;
+
+/// Current transform plus saved copies, for drawing hierarchies.
+///
+/// `transform` applies a matrix in the local frame of everything already on
+/// the stack: with row vectors the result is `matrix * current`, so a child's
+/// own motion happens before its parent's. `push` saves the current matrix
+/// and `pop` restores it.
+///
+/// ```text
+/// stack.transform(&body_to_world);
+/// stack.push();
+/// stack.transform(&wheel_to_body);
+/// draw(wheel, stack.top());
+/// stack.pop();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[allow(dead_code)]
+pub struct MatrixStack {
+    current: Matrix4,
+    saved: Vec<Matrix4>,
+}
+
+#[allow(dead_code)]
+impl MatrixStack {
+    pub fn new() -> Self {
+        Self {
+            current: Matrix4::identity(),
+            saved: Vec::new(),
+        }
+    }
+
+    pub fn top(&self) -> Matrix4 {
+        return self.current;
+    }
+
+    /// Number of saved matrices below the current one.
+    pub fn depth(&self) -> usize {
+        return self.saved.len();
+    }
+
+    pub fn push(&mut self) {
+        self.saved.push(self.current);
+    }
+
+    /// Restores the last saved matrix and returns the one it replaces, or
+    /// `None` without changing anything if nothing was pushed.
+    pub fn pop(&mut self) -> Option<Matrix4> {
+        let restored = self.saved.pop()?;
+        return Some(std::mem::replace(&mut self.current, restored));
+    }
+
+    pub fn transform(&mut self, matrix: &Matrix4) {
+        self.current = *matrix * self.current;
+    }
+
+    /// Replaces the current matrix, leaving saved ones untouched.
+    pub fn load(&mut self, matrix: Matrix4) {
+        self.current = matrix;
+    }
+
+    pub fn load_identity(&mut self) {
+        self.load(Matrix4::identity());
+    }
+
+    pub fn apply(&self, point: &Point) -> Point {
+        return point.apply_matrix(&self.current);
+    }
+}
+
+impl Default for MatrixStack {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
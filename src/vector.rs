@@ -0,0 +1,377 @@
+use core::fmt;
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
+};
+
+use crate::matrix::fixed::{Matrix4, SMatrix};
+use crate::matrix::{Matrix, MatrixError};
+use crate::solid::Point;
+
+/// Direction or position in 3D space.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vec3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+/// Homogeneous coordinates `(x, y, z, w)`. Positions have `w = 1` and
+/// directions `w = 0`; after a projection `w` holds the perspective divisor.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vec4 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+/// The plane `ax + by + cz + d = 0`, stored with a unit normal `(a, b, c)`
+/// so that `signed_distance` is a true distance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    pub normal: Vec3,
+    pub d: f32,
+}
+
+impl Vec3 {
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn zero() -> Self {
+        return Self::default();
+    }
+
+    pub fn dot(self, v: Vec3) -> f32 {
+        return self.x * v.x + self.y * v.y + self.z * v.z;
+    }
+
+    /// Right-handed cross product, perpendicular to both inputs.
+    pub fn cross(self, v: Vec3) -> Vec3 {
+        return Vec3 {
+            x: self.y * v.z - self.z * v.y,
+            y: self.z * v.x - self.x * v.z,
+            z: self.x * v.y - self.y * v.x,
+        };
+    }
+
+    pub fn length_squared(self) -> f32 {
+        return self.dot(self);
+    }
+
+    pub fn length(self) -> f32 {
+        return self.length_squared().sqrt();
+    }
+
+    pub fn distance(self, v: Vec3) -> f32 {
+        return (self - v).length();
+    }
+
+    /// Unit vector in the same direction, or `None` for the zero vector.
+    pub fn normalize(self) -> Option<Vec3> {
+        let length = self.length();
+        if length == 0.0 || !length.is_finite() {
+            return None;
+        }
+        return Some(self / length);
+    }
+
+    /// `self` at `t = 0`, `v` at `t = 1`.
+    pub fn lerp(self, v: Vec3, t: f32) -> Vec3 {
+        return self + (v - self) * t;
+    }
+
+    /// Homogeneous vector with the given `w`.
+    pub fn extend(self, w: f32) -> Vec4 {
+        return Vec4::new(self.x, self.y, self.z, w);
+    }
+}
+
+impl Vec4 {
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self { x, y, z, w }
+    }
+
+    pub fn zero() -> Self {
+        return Self::default();
+    }
+
+    pub fn dot(self, v: Vec4) -> f32 {
+        return self.x * v.x + self.y * v.y + self.z * v.z + self.w * v.w;
+    }
+
+    pub fn length_squared(self) -> f32 {
+        return self.dot(self);
+    }
+
+    pub fn length(self) -> f32 {
+        return self.length_squared().sqrt();
+    }
+
+    /// Unit vector in the same direction, or `None` for the zero vector.
+    pub fn normalize(self) -> Option<Vec4> {
+        let length = self.length();
+        if length == 0.0 || !length.is_finite() {
+            return None;
+        }
+        return Some(self / length);
+    }
+
+    /// `self` at `t = 0`, `v` at `t = 1`.
+    pub fn lerp(self, v: Vec4, t: f32) -> Vec4 {
+        return self + (v - self) * t;
+    }
+
+    /// Drops `w` without dividing by it.
+    pub fn truncate(self) -> Vec3 {
+        return Vec3::new(self.x, self.y, self.z);
+    }
+
+    /// Divides by `w`, or `None` for a direction (`w = 0`).
+    pub fn project(self) -> Option<Vec3> {
+        if self.w == 0.0 {
+            return None;
+        }
+        return Some(self.truncate() / self.w);
+    }
+}
+
+impl Plane {
+    /// Scales the coefficients to a unit normal, or `None` if `(a, b, c)` is
+    /// the zero vector.
+    pub fn new(a: f32, b: f32, c: f32, d: f32) -> Option<Plane> {
+        let length = Vec3::new(a, b, c).length();
+        if length == 0.0 || !length.is_finite() {
+            return None;
+        }
+        return Some(Plane {
+            normal: Vec3::new(a, b, c) / length,
+            d: d / length,
+        });
+    }
+
+    /// The plane through `point` facing along `normal`.
+    pub fn from_point_normal(point: Vec3, normal: Vec3) -> Option<Plane> {
+        return Plane::new(normal.x, normal.y, normal.z, -normal.dot(point));
+    }
+
+    /// Positive on the side the normal points to.
+    pub fn signed_distance(&self, p: Vec3) -> f32 {
+        return self.normal.dot(p) + self.d;
+    }
+
+    /// `(a, b, c, d)`, so that a homogeneous point lies on the plane when its
+    /// dot product with this is zero.
+    pub fn coefficients(&self) -> Vec4 {
+        return self.normal.extend(self.d);
+    }
+}
+
+/// Component-wise arithmetic shared by both vector types.
+macro_rules! impl_vector_ops {
+    ($v:ident, $len:expr, $($field:ident => $i:expr),+) => {
+        impl Add for $v {
+            type Output = $v;
+
+            fn add(self, rhs: $v) -> $v {
+                return $v { $($field: self.$field + rhs.$field),+ };
+            }
+        }
+
+        impl Sub for $v {
+            type Output = $v;
+
+            fn sub(self, rhs: $v) -> $v {
+                return $v { $($field: self.$field - rhs.$field),+ };
+            }
+        }
+
+        impl Neg for $v {
+            type Output = $v;
+
+            fn neg(self) -> $v {
+                return $v { $($field: -self.$field),+ };
+            }
+        }
+
+        impl Mul<f32> for $v {
+            type Output = $v;
+
+            fn mul(self, rhs: f32) -> $v {
+                return $v { $($field: self.$field * rhs),+ };
+            }
+        }
+
+        impl Mul<$v> for f32 {
+            type Output = $v;
+
+            fn mul(self, rhs: $v) -> $v {
+                return rhs * self;
+            }
+        }
+
+        impl Div<f32> for $v {
+            type Output = $v;
+
+            fn div(self, rhs: f32) -> $v {
+                return $v { $($field: self.$field / rhs),+ };
+            }
+        }
+
+        impl AddAssign for $v {
+            fn add_assign(&mut self, rhs: $v) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $v {
+            fn sub_assign(&mut self, rhs: $v) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl MulAssign<f32> for $v {
+            fn mul_assign(&mut self, rhs: f32) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl DivAssign<f32> for $v {
+            fn div_assign(&mut self, rhs: f32) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl Index<usize> for $v {
+            type Output = f32;
+
+            fn index(&self, index: usize) -> &f32 {
+                match index {
+                    $($i => return &self.$field,)+
+                    _ => panic!("index {} out of bounds for a {}-vector", index, $len),
+                }
+            }
+        }
+
+        impl IndexMut<usize> for $v {
+            fn index_mut(&mut self, index: usize) -> &mut f32 {
+                match index {
+                    $($i => return &mut self.$field,)+
+                    _ => panic!("index {} out of bounds for a {}-vector", index, $len),
+                }
+            }
+        }
+
+        impl From<[f32; $len]> for $v {
+            fn from(a: [f32; $len]) -> $v {
+                return $v { $($field: a[$i]),+ };
+            }
+        }
+
+        impl From<$v> for [f32; $len] {
+            fn from(v: $v) -> [f32; $len] {
+                return [$(v.$field),+];
+            }
+        }
+
+        /// A single-row matrix.
+        impl From<$v> for SMatrix<1, $len> {
+            fn from(v: $v) -> SMatrix<1, $len> {
+                return SMatrix::from_array([[$(v.$field),+]]);
+            }
+        }
+
+        impl From<SMatrix<1, $len>> for $v {
+            fn from(m: SMatrix<1, $len>) -> $v {
+                return $v { $($field: m[(0, $i)]),+ };
+            }
+        }
+
+        /// A 1 x n `Matrix`.
+        impl From<$v> for Matrix {
+            fn from(v: $v) -> Matrix {
+                return Matrix::from_array([[$(v.$field),+]]);
+            }
+        }
+
+        /// A matrix row, as returned by `Matrix::row`.
+        impl TryFrom<&[f32]> for $v {
+            type Error = MatrixError;
+
+            fn try_from(row: &[f32]) -> Result<$v, MatrixError> {
+                if row.len() != $len {
+                    return Err(MatrixError::DataLength {
+                        expected: $len,
+                        found: row.len(),
+                    });
+                }
+                return Ok($v { $($field: row[$i]),+ });
+            }
+        }
+    };
+}
+
+impl_vector_ops!(Vec3, 3, x => 0, y => 1, z => 2);
+impl_vector_ops!(Vec4, 4, x => 0, y => 1, z => 2, w => 3);
+
+/// Row vector times matrix, the convention every transform in this crate
+/// uses.
+impl Mul<Matrix4> for Vec4 {
+    type Output = Vec4;
+
+    fn mul(self, m: Matrix4) -> Vec4 {
+        // Summed from zero in the same order as `matrix_multiply_blocked`, so
+        // batched transforms match this bit for bit, signed zeros included.
+        let v = [self.x, self.y, self.z, self.w];
+        let column = |j: usize| {
+            return (0..4).fold(0.0, |sum, k| sum + v[k] * m[(k, j)]);
+        };
+        return Vec4::new(column(0), column(1), column(2), column(3));
+    }
+}
+
+impl MulAssign<Matrix4> for Vec4 {
+    fn mul_assign(&mut self, m: Matrix4) {
+        *self = *self * m;
+    }
+}
+
+impl From<&Point> for Vec3 {
+    fn from(p: &Point) -> Vec3 {
+        return Vec3::new(p.x, p.y, p.z);
+    }
+}
+
+impl From<Point> for Vec3 {
+    fn from(p: Point) -> Vec3 {
+        return Vec3::from(&p);
+    }
+}
+
+impl From<Vec3> for Point {
+    fn from(v: Vec3) -> Point {
+        return Point::from_f32(v.x, v.y, v.z);
+    }
+}
+
+impl fmt::Display for Vec3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "({}, {}, {})", self.x, self.y, self.z);
+    }
+}
+
+impl fmt::Display for Plane {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(
+            f,
+            "{}x + {}y + {}z + {} = 0",
+            self.normal.x, self.normal.y, self.normal.z, self.d
+        );
+    }
+}
+
+impl fmt::Display for Vec4 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "({}, {}, {}, {})", self.x, self.y, self.z, self.w);
+    }
+}